@@ -0,0 +1,143 @@
+use std::io::BufRead;
+
+use aoc::{Answer, Error, Example, PuzzlePart, Result, Solution, Solver};
+
+mod matcher;
+
+use matcher::Matcher;
+pub use matcher::Vocabulary;
+
+/// Digit value of an ASCII digit byte. Bytes of multi-byte UTF-8 characters are all >= 0x80, so
+/// they never match.
+fn ascii_digit(byte: u8) -> Option<u8> {
+    byte.is_ascii_digit().then(|| byte - b'0')
+}
+
+fn parse_calibration_values1(line: &str) -> Result<u8> {
+    let mut digits = line.bytes().filter_map(ascii_digit);
+    let first_digit = digits.next();
+    let last_digit = digits.next_back();
+
+    combine_digits(line, first_digit, last_digit)
+}
+
+/// Combines the first and last digits found on `line` into a two-digit number.
+fn combine_digits(line: &str, first_digit: Option<u8>, last_digit: Option<u8>) -> Result<u8> {
+    let first_digit = first_digit
+        .ok_or_else(|| Error::parse(format!("Expected at least 1 digit. Got '{line}'.")))?;
+    let last_digit = last_digit.unwrap_or(first_digit);
+    Ok(first_digit * 10 + last_digit)
+}
+
+/// Like `parse_calibration_values1`, but digits may also be spelled out with any word from the
+/// matcher's vocabulary. Overlapping words all count, e.g. "twone" has the digits 2 and 1.
+fn parse_calibration_values2(line: &str, matcher: &Matcher) -> Result<u8> {
+    let bytes = line.as_bytes();
+    combine_digits(line, matcher.first(bytes), matcher.last(bytes))
+}
+
+fn read_lines<R: BufRead>(reader: R) -> Result<Vec<String>> {
+    Ok(reader.lines().collect::<std::io::Result<_>>()?)
+}
+
+fn sum_calibration_values(lines: &[String], parse: impl Fn(&str) -> Result<u8>) -> Result<u64> {
+    let mut acc: u64 = 0;
+    for (line_idx, line) in lines.iter().enumerate() {
+        let lineno = line_idx + 1;
+        let value = parse(line).map_err(|err| err.at_line(lineno))?;
+        acc += value as u64;
+    }
+    Ok(acc)
+}
+
+fn solve_1(lines: &[String]) -> Result<u64> {
+    sum_calibration_values(lines, parse_calibration_values1)
+}
+
+fn solve_2(lines: &[String], vocabulary: &Vocabulary) -> Result<u64> {
+    let matcher = Matcher::new(vocabulary);
+    sum_calibration_values(lines, |line| parse_calibration_values2(line, &matcher))
+}
+
+pub fn run_1<R: BufRead>(reader: R) -> Result<u64> {
+    solve_1(&read_lines(reader)?)
+}
+
+pub fn run_2<R: BufRead>(reader: R, vocabulary: &Vocabulary) -> Result<u64> {
+    solve_2(&read_lines(reader)?, vocabulary)
+}
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        path: concat!(env!("CARGO_MANIFEST_DIR"), "/example1.txt"),
+        part_1: Some("142"),
+        part_2: Some("142"),
+    },
+    Example {
+        path: concat!(env!("CARGO_MANIFEST_DIR"), "/example2.txt"),
+        part_1: None,
+        part_2: Some("281"),
+    },
+];
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, part: PuzzlePart, reader: &mut dyn BufRead) -> Result<Solver> {
+        let lines = read_lines(reader)?;
+        Ok(match part {
+            PuzzlePart::One => Box::new(move || solve_1(&lines).map(Answer::from)),
+            PuzzlePart::Two => {
+                Box::new(move || solve_2(&lines, &Vocabulary::default()).map(Answer::from))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_parse_calibration_values {
+    use super::*;
+
+    #[test]
+    fn test_digits_only() {
+        assert_eq!(parse_calibration_values1("a1b2c3").unwrap(), 13);
+        assert_eq!(parse_calibration_values1("treb7uchet").unwrap(), 77);
+        assert_eq!(parse_calibration_values1("two1nine").unwrap(), 11);
+        assert!(parse_calibration_values1("nodigits").is_err());
+    }
+
+    fn parse_calibration_values2(line: &str) -> Result<u8> {
+        super::parse_calibration_values2(line, &Matcher::new(&Vocabulary::default()))
+    }
+
+    #[test]
+    fn test_overlapping_words() {
+        assert_eq!(parse_calibration_values2("twone").unwrap(), 21);
+        assert_eq!(parse_calibration_values2("eightwo").unwrap(), 82);
+        assert_eq!(parse_calibration_values2("oneight").unwrap(), 18);
+        assert_eq!(parse_calibration_values2("3twoneight").unwrap(), 38);
+        assert_eq!(parse_calibration_values2("eighthree").unwrap(), 83);
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        assert_eq!(parse_calibration_values1("é1ü€2日").unwrap(), 12);
+        assert_eq!(parse_calibration_values2("ñtwo€日本seveñ").unwrap(), 22);
+        assert_eq!(parse_calibration_values2("😀one😀8😀").unwrap(), 18);
+        // Neither full-width nor non-Latin digits count.
+        assert!(parse_calibration_values1("１２٣").is_err());
+        assert!(parse_calibration_values2("ｏｎｅ").is_err());
+    }
+}
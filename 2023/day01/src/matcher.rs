@@ -0,0 +1,263 @@
+//! Finds the first and last digit on a line, spelled or not, with an Aho-Corasick automaton.
+//!
+//! The words are compiled into one automaton for scanning forward, and one for their reversed
+//! spellings for scanning backward. Each scan stops soon after its first match, so every byte of a
+//! line is looked at about once.
+use std::fs;
+
+use aoc::{parse_number, Error, Result};
+
+/// Words that stand for digits, in addition to the ASCII digits themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u8)>,
+}
+
+impl Default for Vocabulary {
+    fn default() -> Self {
+        let words = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        Self {
+            words: (1..)
+                .zip(words)
+                .map(|(digit, word)| (word.to_string(), digit))
+                .collect(),
+        }
+    }
+}
+
+impl Vocabulary {
+    /// Parses lines of `<word> <digit>`, e.g. `zero 0`. Empty lines and `#` comments are ignored.
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut words: Vec<(String, u8)> = Vec::new();
+        for (line_idx, line) in s.lines().enumerate() {
+            let lineno = line_idx + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [word, digit] = fields[..] else {
+                return Err(
+                    Error::parse(format!("Expected '<word> <digit>'. Got '{line}'."))
+                        .at_line(lineno),
+                );
+            };
+            let digit: u8 = parse_number(digit).map_err(|err| err.at_line(lineno))?;
+            if digit > 9 {
+                return Err(
+                    Error::parse(format!("Expected a digit from 0 to 9. Got '{digit}'."))
+                        .at_line(lineno),
+                );
+            }
+            if words.iter().any(|(other, _)| other == word) {
+                return Err(
+                    Error::parse(format!("Word '{word}' given more than once.")).at_line(lineno)
+                );
+            }
+            words.push((word.to_string(), digit));
+        }
+        Ok(Self { words })
+    }
+
+    pub fn read(path: &str) -> Result<Self> {
+        let s = fs::read_to_string(path).map_err(|err| Error::io(path, err))?;
+        Self::parse(&s)
+    }
+
+    /// The words, followed by the ASCII digits.
+    fn patterns(&self) -> impl Iterator<Item = (&[u8], u8)> {
+        const DIGITS: &[u8; 10] = b"0123456789";
+        self.words
+            .iter()
+            .map(|(word, digit)| (word.as_bytes(), *digit))
+            .chain((0..10).map(|digit| (&DIGITS[digit..digit + 1], digit as u8)))
+    }
+}
+
+pub struct Matcher {
+    forward: Automaton,
+    backward: Automaton,
+}
+
+impl Matcher {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let forward = Automaton::new(
+            vocabulary
+                .patterns()
+                .map(|(word, digit)| (word.to_vec(), digit)),
+        );
+        let backward = Automaton::new(vocabulary.patterns().map(|(word, digit)| {
+            let mut word = word.to_vec();
+            word.reverse();
+            (word, digit)
+        }));
+        Self { forward, backward }
+    }
+
+    /// The digit of the word that starts first in `line`.
+    pub fn first(&self, line: &[u8]) -> Option<u8> {
+        self.forward.find(line.iter().copied())
+    }
+
+    /// The digit of the word that ends last in `line`.
+    pub fn last(&self, line: &[u8]) -> Option<u8> {
+        self.backward.find(line.iter().rev().copied())
+    }
+}
+
+const ROOT: usize = 0;
+
+#[derive(Debug, Copy, Clone)]
+struct Match {
+    len: usize,
+    digit: u8,
+}
+
+struct Node {
+    /// State to go to on each byte.
+    next: [usize; 256],
+    /// Words that end here: this node's own, and those of the nodes its suffixes lead to.
+    matches: Vec<Match>,
+}
+
+/// A deterministic automaton over bytes.
+struct Automaton {
+    nodes: Vec<Node>,
+    max_len: usize,
+}
+
+impl Automaton {
+    fn new(patterns: impl Iterator<Item = (Vec<u8>, u8)>) -> Self {
+        const MISSING: usize = usize::MAX;
+        let new_node = || Node {
+            next: [MISSING; 256],
+            matches: Vec::new(),
+        };
+
+        // Build the trie.
+        let mut nodes = vec![new_node()];
+        let mut max_len = 0;
+        for (pattern, digit) in patterns {
+            let mut state = ROOT;
+            for &byte in &pattern {
+                if nodes[state].next[byte as usize] == MISSING {
+                    nodes.push(new_node());
+                    nodes[state].next[byte as usize] = nodes.len() - 1;
+                }
+                state = nodes[state].next[byte as usize];
+            }
+            let len = pattern.len();
+            nodes[state].matches.push(Match { len, digit });
+            max_len = max_len.max(len);
+        }
+
+        // Breadth-first, so that a node's failure link is done before the node itself. Missing
+        // transitions are filled in with those of the failure link, i.e. the longest proper suffix
+        // that is also in the trie.
+        let mut fail = vec![ROOT; nodes.len()];
+        let mut queue = std::collections::VecDeque::from([ROOT]);
+        while let Some(state) = queue.pop_front() {
+            for byte in 0..256 {
+                let child = nodes[state].next[byte];
+                let fallback = if state == ROOT {
+                    ROOT
+                } else {
+                    nodes[fail[state]].next[byte]
+                };
+                if child == MISSING {
+                    nodes[state].next[byte] = fallback;
+                } else {
+                    fail[child] = fallback;
+                    let inherited = nodes[fallback].matches.clone();
+                    nodes[child].matches.extend(inherited);
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        Self { nodes, max_len }
+    }
+
+    /// Returns the digit of the match that starts first in `haystack`, preferring the longest one
+    /// if several start at the same byte.
+    fn find(&self, haystack: impl Iterator<Item = u8>) -> Option<u8> {
+        // (start, len, digit)
+        let mut best: Option<(usize, usize, u8)> = None;
+        let mut state = ROOT;
+        for (pos, byte) in haystack.enumerate() {
+            if let Some((start, _, _)) = best {
+                // Anything that starts as early would have ended by now.
+                if pos >= start + self.max_len {
+                    break;
+                }
+            }
+            state = self.nodes[state].next[byte as usize];
+            for m in &self.nodes[state].matches {
+                let start = pos + 1 - m.len;
+                let is_better = match best {
+                    None => true,
+                    Some((best_start, best_len, _)) => {
+                        start < best_start || (start == best_start && m.len > best_len)
+                    }
+                };
+                if is_better {
+                    best = Some((start, m.len, m.digit));
+                }
+            }
+        }
+        best.map(|(_, _, digit)| digit)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_Matcher {
+    use super::*;
+
+    fn first_and_last(matcher: &Matcher, line: &str) -> (Option<u8>, Option<u8>) {
+        (
+            matcher.first(line.as_bytes()),
+            matcher.last(line.as_bytes()),
+        )
+    }
+
+    #[test]
+    fn test_default() {
+        let matcher = Matcher::new(&Vocabulary::default());
+        assert_eq!(first_and_last(&matcher, "two1nine"), (Some(2), Some(9)));
+        assert_eq!(first_and_last(&matcher, "zoneight234"), (Some(1), Some(4)));
+        assert_eq!(
+            first_and_last(&matcher, "7pqrstsixteen"),
+            (Some(7), Some(6))
+        );
+        assert_eq!(first_and_last(&matcher, "twone"), (Some(2), Some(1)));
+        assert_eq!(first_and_last(&matcher, "0"), (Some(0), Some(0)));
+        assert_eq!(first_and_last(&matcher, "nothing"), (None, None));
+    }
+
+    #[test]
+    fn test_nested_words() {
+        // "elev" and "eleven" start at the same byte, so the longer one wins.
+        let vocabulary = Vocabulary::parse("eleven 1\neven 2\nleve 3\nelev 4").unwrap();
+        let matcher = Matcher::new(&vocabulary);
+        assert_eq!(first_and_last(&matcher, "xelevenx"), (Some(1), Some(1)));
+        assert_eq!(first_and_last(&matcher, "elevxeven"), (Some(4), Some(2)));
+    }
+
+    #[test]
+    fn test_custom_vocabulary() {
+        let vocabulary = Vocabulary::parse("# German\nnull 0\neins 1\nzwei 2\n").unwrap();
+        let matcher = Matcher::new(&vocabulary);
+        assert_eq!(first_and_last(&matcher, "nullzweins"), (Some(0), Some(1)));
+        assert_eq!(first_and_last(&matcher, "one"), (None, None));
+    }
+
+    #[test]
+    fn test_invalid_vocabulary() {
+        assert!(Vocabulary::parse("ten 10").is_err());
+        assert!(Vocabulary::parse("one").is_err());
+        assert!(Vocabulary::parse("one 1\none 2").is_err());
+    }
+}
@@ -0,0 +1,273 @@
+use std::cmp;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use aoc::{parse_number, regex, Error, Example, PuzzlePart, Result, Solution, Solver};
+use log::debug;
+
+/// Number of cubes per color.
+type CubeCounts = BTreeMap<String, u64>;
+
+/// How many cubes of each color the bag holds. Colors that aren't listed aren't in the bag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    limits: CubeCounts,
+}
+
+impl Default for Bag {
+    fn default() -> Self {
+        Self {
+            limits: CubeCounts::from([
+                ("red".to_string(), 12),
+                ("green".to_string(), 13),
+                ("blue".to_string(), 14),
+            ]),
+        }
+    }
+}
+
+impl FromStr for Bag {
+    type Err = Error;
+
+    /// Parses e.g. `red=12,green=13,blue=14`.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut limits = CubeCounts::new();
+        for entry in s.split(',').map(|entry| entry.trim()) {
+            let (color, count) = entry.split_once('=').ok_or_else(|| {
+                Error::parse(format!("Expected '<color>=<count>'. Got '{entry}'."))
+            })?;
+            let color = color.trim();
+            if color.is_empty() {
+                return Err(Error::parse(format!("Missing color in '{entry}'.")));
+            }
+            let count = parse_number(count.trim())?;
+            if limits.insert(color.to_string(), count).is_some() {
+                return Err(Error::parse(format!(
+                    "Color '{color}' given more than once."
+                )));
+            }
+        }
+        Ok(Self { limits })
+    }
+}
+
+struct GameSet {
+    counts: CubeCounts,
+}
+
+impl GameSet {
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut counts = CubeCounts::new();
+        let roll_re = regex!(r"^(\d+) (\S+)$");
+        let rolls = s.split(',').map(|s| s.trim());
+        for roll in rolls {
+            let caps = roll_re
+                .captures(roll)
+                .ok_or_else(|| Error::parse(format!("Invalid roll string: '{roll}'")))?;
+            let num: u64 = parse_number(&caps[1])?;
+            let color = &caps[2];
+            if counts.insert(color.to_string(), num).is_some() {
+                return Err(Error::parse(format!(
+                    "Color '{color}' given more than once in '{s}'."
+                )));
+            }
+        }
+        Ok(Self { counts })
+    }
+
+    pub fn check_possible(&self, bag: &Bag) -> Result<()> {
+        let mut errors = Vec::new();
+        for (name, &actual) in &self.counts {
+            let max = bag.limits.get(name).copied().unwrap_or(0);
+            if actual > max {
+                errors.push(format!("expected at most {max} {name}, got {actual}"))
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::validation(errors.join("; ")))
+        }
+    }
+}
+
+struct Game {
+    id: u64,
+    sets: Vec<GameSet>,
+}
+
+impl Game {
+    pub fn parse(line: &str) -> Result<Self> {
+        let header_re = regex!(r"^Game (\d+):(.*)");
+        let caps = header_re.captures(line).ok_or_else(|| {
+            Error::parse(format!("Line does not start with a game header: '{line}'"))
+        })?;
+
+        let game_id: u64 = parse_number(&caps[1])?;
+
+        let body = &caps[2];
+
+        let mut sets: Vec<GameSet> = Vec::new();
+        for s in body.split(';').map(|s| s.trim()) {
+            let set = GameSet::parse(s)?;
+            sets.push(set);
+        }
+        Ok(Self { id: game_id, sets })
+    }
+
+    pub fn check_possible(&self, bag: &Bag) -> Result<()> {
+        let mut impossible: Vec<String> = Vec::new();
+        for (set_idx, set) in self.sets.iter().enumerate() {
+            let setno = set_idx + 1;
+            if let Err(err) = set.check_possible(bag) {
+                impossible.push(format!("Set {setno} is impossible: {err}."));
+            }
+        }
+
+        if impossible.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::validation(impossible.join(" ")))
+        }
+    }
+
+    /// Multiplies together the counts of the smallest bag that makes the game possible. Colors
+    /// of `bag` that were never seen count as 0.
+    fn compute_power(&self, bag: &Bag) -> u64 {
+        let mut smallest_bag = Bag {
+            limits: bag.limits.keys().map(|name| (name.clone(), 0)).collect(),
+        };
+        for set in self.sets.iter() {
+            for (name, &count) in &set.counts {
+                let limit = smallest_bag.limits.entry(name.clone()).or_insert(0);
+                *limit = cmp::max(*limit, count);
+            }
+        }
+        smallest_bag.limits.values().product()
+    }
+}
+
+fn read_games<R: BufRead>(reader: R) -> Result<Vec<Game>> {
+    reader
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| -> Result<Game> {
+            let lineno = line_idx + 1;
+            let line = line?;
+
+            let game = Game::parse(&line).map_err(|err| err.at_line(lineno))?;
+            Ok(game)
+        })
+        .collect()
+}
+
+fn solve_1(games: &[Game], bag: &Bag) -> u64 {
+    let mut acc: u64 = 0;
+    for game in games {
+        match game.check_possible(bag) {
+            Ok(_) => acc += game.id,
+            Err(err) => debug!("Game {} is not possible. {}", game.id, err),
+        }
+    }
+    acc
+}
+
+fn solve_2(games: &[Game], bag: &Bag) -> u64 {
+    let mut acc: u64 = 0;
+    for game in games {
+        let power = game.compute_power(bag);
+        acc += power;
+    }
+    acc
+}
+
+pub fn run_1<R: BufRead>(reader: R, bag: &Bag) -> Result<u64> {
+    Ok(solve_1(&read_games(reader)?, bag))
+}
+
+pub fn run_2<R: BufRead>(reader: R, bag: &Bag) -> Result<u64> {
+    Ok(solve_2(&read_games(reader)?, bag))
+}
+
+pub const EXAMPLES: &[Example] = &[Example {
+    path: concat!(env!("CARGO_MANIFEST_DIR"), "/example.txt"),
+    part_1: Some("8"),
+    part_2: Some("2286"),
+}];
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, part: PuzzlePart, reader: &mut dyn BufRead) -> Result<Solver> {
+        let games = read_games(reader)?;
+        Ok(match part {
+            PuzzlePart::One => Box::new(move || Ok(solve_1(&games, &Bag::default()).into())),
+            PuzzlePart::Two => Box::new(move || Ok(solve_2(&games, &Bag::default()).into())),
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_Bag {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "red=12,green=13,blue=14".parse::<Bag>().unwrap(),
+            Bag::default()
+        );
+        assert!("red=12,red=13".parse::<Bag>().is_err());
+        assert!("red".parse::<Bag>().is_err());
+        assert!("=12".parse::<Bag>().is_err());
+    }
+
+    #[test]
+    fn test_extra_colors() {
+        let game = Game::parse("Game 1: 3 purple, 2 red; 1 purple, 4 red").unwrap();
+        let bag: Bag = "red=12,purple=2".parse().unwrap();
+        assert!(game.check_possible(&bag).is_err());
+        let bag: Bag = "red=12,purple=3".parse().unwrap();
+        assert!(game.check_possible(&bag).is_ok());
+        assert!(game.check_possible(&Bag::default()).is_err());
+        assert_eq!(game.compute_power(&bag), 12);
+        assert_eq!(game.compute_power(&Bag::default()), 0);
+    }
+
+    #[test]
+    fn test_missing_color() {
+        let game = Game::parse("Game 1: 3 red, 4 blue").unwrap();
+        assert_eq!(game.compute_power(&Bag::default()), 0);
+        let game = Game::parse("Game 1: 3 red, 4 blue; 1 green").unwrap();
+        assert_eq!(game.compute_power(&Bag::default()), 12);
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_Game {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert!(Game::parse("Game 1: 3 red, 4 blue; 1 green").is_ok());
+        assert!(Game::parse("Game 1: 3 red, 4 red").is_err());
+        assert!(Game::parse("Game 1: 3 red junk").is_err());
+        assert!(Game::parse("Game 1: x3 red").is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use std::io::BufRead;
+
+use aoc::grid::{Coord, Grid};
+use aoc::{parse_number, Error, Example, Lines, PuzzlePart, Result, Solution, Solver};
+
+pub fn run_1<R: BufRead>(reader: R) -> Result<u64> {
+    Ok(solve_1(&parse_grid(reader)?))
+}
+
+pub fn run_2<R: BufRead>(reader: R) -> Result<u64> {
+    Ok(solve_2(&parse_grid(reader)?))
+}
+
+fn solve_1(schematic: &Schematic) -> u64 {
+    schematic
+        .numbers
+        .iter()
+        .filter(|number| schematic.touches_symbol(number))
+        .map(|number| number.value)
+        .sum()
+}
+
+fn solve_2(schematic: &Schematic) -> u64 {
+    let mut acc: u64 = 0;
+    for symbol in schematic.symbols.iter().filter(|&s| s.value == '*') {
+        let adjacent = schematic.numbers_adjacent_to(symbol);
+        if adjacent.len() != 2 {
+            continue;
+        }
+        let gear_ratio = adjacent[0].value * adjacent[1].value;
+        acc += gear_ratio;
+    }
+    acc
+}
+
+pub const EXAMPLES: &[Example] = &[Example {
+    path: concat!(env!("CARGO_MANIFEST_DIR"), "/example.txt"),
+    part_1: Some("4361"),
+    part_2: Some("467835"),
+}];
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, part: PuzzlePart, reader: &mut dyn BufRead) -> Result<Solver> {
+        let schematic = parse_grid(reader)?;
+        Ok(match part {
+            PuzzlePart::One => Box::new(move || Ok(solve_1(&schematic).into())),
+            PuzzlePart::Two => Box::new(move || Ok(solve_2(&schematic).into())),
+        })
+    }
+}
+
+type NumberId = usize;
+
+struct IdGenerator {
+    curr: NumberId,
+}
+
+impl IdGenerator {
+    pub fn new() -> Self {
+        IdGenerator { curr: 0 }
+    }
+
+    pub fn next(&mut self) -> NumberId {
+        let ret = self.curr;
+        self.curr += 1;
+        ret
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Number {
+    id: NumberId,
+    value: u64,
+    /// Leftmost digit.
+    start: Coord,
+    len: usize,
+}
+
+impl Number {
+    /// Cells covered by the digits.
+    fn coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        (0..self.len).map(|dx| Coord::new(self.start.x + dx, self.start.y))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Symbol {
+    value: char,
+    coord: Coord,
+}
+
+/// Finds the numbers and symbols on row `y`.
+fn parse_row(
+    row: &[char],
+    y: usize,
+    id_gen: &mut IdGenerator,
+) -> Result<(Vec<Number>, Vec<Symbol>)> {
+    let mut numbers: Vec<Number> = Vec::new();
+    let mut symbols: Vec<Symbol> = Vec::new();
+
+    let mut x = 0;
+    while x < row.len() {
+        let c = row[x];
+        if c.is_ascii_digit() {
+            let len = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+            let digits: String = row[x..x + len].iter().collect();
+            let value = parse_number(&digits).map_err(|err| err.at_column(x + 1))?;
+            numbers.push(Number {
+                id: id_gen.next(),
+                value,
+                start: Coord::new(x, y),
+                len,
+            });
+            x += len;
+        } else {
+            if is_symbol(c) {
+                symbols.push(Symbol {
+                    value: c,
+                    coord: Coord::new(x, y),
+                });
+            }
+            x += 1;
+        }
+    }
+    Ok((numbers, symbols))
+}
+
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+struct Schematic {
+    grid: Grid<char>,
+    /// Indexed by `NumberId`.
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    /// Which number, if any, covers each cell.
+    number_ids: Grid<Option<NumberId>>,
+}
+
+impl Schematic {
+    /// Finds numbers adjacent to `symbol`, each once.
+    fn numbers_adjacent_to(&self, symbol: &Symbol) -> Vec<&Number> {
+        let mut ids: Vec<NumberId> = self
+            .grid
+            .neighbors8(symbol.coord)
+            .filter_map(|coord| self.number_ids[coord])
+            .collect();
+        // A number can cover several neighbors of the same symbol.
+        ids.sort_unstable();
+        ids.dedup();
+        ids.into_iter().map(|id| &self.numbers[id]).collect()
+    }
+
+    /// Whether any symbol is adjacent to `number`.
+    fn touches_symbol(&self, number: &Number) -> bool {
+        number.coords().any(|coord| {
+            self.grid
+                .neighbors8(coord)
+                .any(|neighbor| is_symbol(self.grid[neighbor]))
+        })
+    }
+}
+
+fn parse_grid<R: BufRead>(reader: R) -> Result<Schematic> {
+    let mut lines = Lines::new(reader);
+    let grid = Grid::read(&mut lines, Ok)?;
+    for line in lines {
+        let (lineno, line) = line?;
+        if !line.is_empty() {
+            return Err(Error::parse(format!(
+                "Expected only empty lines after the schematic. Got '{line}'."
+            ))
+            .at_line(lineno));
+        }
+    }
+
+    let mut id_gen = IdGenerator::new();
+    let mut numbers: Vec<Number> = Vec::new();
+    let mut symbols: Vec<Symbol> = Vec::new();
+    for (y, row) in grid.rows().enumerate() {
+        let lineno = y + 1;
+        let (row_numbers, row_symbols) =
+            parse_row(row, y, &mut id_gen).map_err(|err| err.at_line(lineno))?;
+        numbers.extend(row_numbers);
+        symbols.extend(row_symbols);
+    }
+
+    let mut number_ids = Grid::new(grid.width(), grid.height(), None);
+    for number in &numbers {
+        for coord in number.coords() {
+            number_ids[coord] = Some(number.id);
+        }
+    }
+
+    Ok(Schematic {
+        grid,
+        numbers,
+        symbols,
+        number_ids,
+    })
+}
+
+#[cfg(test)]
+mod test_parse_row {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut id_gen = IdGenerator::new();
+        let row: Vec<char> = "467..*114$..#9".chars().collect();
+        let (numbers, symbols) = parse_row(&row, 3, &mut id_gen).unwrap();
+
+        assert_eq!(
+            numbers,
+            vec![
+                Number {
+                    id: 0,
+                    value: 467,
+                    start: Coord::new(0, 3),
+                    len: 3,
+                },
+                Number {
+                    id: 1,
+                    value: 114,
+                    start: Coord::new(6, 3),
+                    len: 3,
+                },
+                Number {
+                    id: 2,
+                    value: 9,
+                    start: Coord::new(13, 3),
+                    len: 1,
+                }
+            ]
+        );
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol {
+                    value: '*',
+                    coord: Coord::new(5, 3)
+                },
+                Symbol {
+                    value: '$',
+                    coord: Coord::new(9, 3)
+                },
+                Symbol {
+                    value: '#',
+                    coord: Coord::new(12, 3)
+                },
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_Schematic {
+    use super::*;
+
+    #[test]
+    fn test_adjacency() {
+        let schematic = parse_grid("123.\n.*..\n..45\n6...\n".as_bytes()).unwrap();
+        let values: Vec<u64> = schematic
+            .numbers_adjacent_to(&schematic.symbols[0])
+            .iter()
+            .map(|number| number.value)
+            .collect();
+        assert_eq!(values, [123, 45]);
+
+        let touching: Vec<bool> = schematic
+            .numbers
+            .iter()
+            .map(|number| schematic.touches_symbol(number))
+            .collect();
+        assert_eq!(touching, [true, true, false]);
+    }
+
+    #[test]
+    fn test_lines_after_empty_line() {
+        assert!(parse_grid("467..\n...*.\n\n".as_bytes()).is_ok());
+        let err = parse_grid("467..\n...*.\n\n..35.\n".as_bytes())
+            .err()
+            .unwrap();
+        assert!(matches!(err, Error::Parse { line: Some(4), .. }));
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+
+use aoc::{
+    parse_number, parse_space_sep_numbers, regex, Answer, Error, Example, PuzzlePart, Result,
+    Solution, Solver,
+};
+use log::{debug, trace};
+
+pub fn run_1<R: BufRead>(reader: R) -> Result<i64> {
+    solve_1(&read_cards(reader)?)
+}
+
+pub fn run_2<R: BufRead>(reader: R) -> Result<i64> {
+    Ok(solve_2(&read_cards(reader)?))
+}
+
+fn read_cards<R: BufRead>(mut reader: R) -> Result<Vec<Card>> {
+    iter_cards(&mut reader)
+        .enumerate()
+        .map(|(idx, card)| {
+            let lineno = idx + 1;
+            card.map_err(|err| err.at_line(lineno))
+        })
+        .collect()
+}
+
+fn solve_1(cards: &[Card]) -> Result<i64> {
+    let mut acc: i64 = 0;
+    for card in cards {
+        let score = card.compute_score()?;
+        acc = acc.checked_add(score).ok_or_else(|| {
+            Error::validation(format!("The total score overflows at card {}.", card.id))
+        })?;
+    }
+    Ok(acc)
+}
+
+fn solve_2(cards: &[Card]) -> i64 {
+    let mut card_copies = HashMap::new();
+    let mut acc = 0;
+    for card in cards {
+        // Always count the original card.
+        acc += 1;
+        let copy_count_curr = card_copies.remove(&card.id).unwrap_or(0);
+        // Accumulate copies.
+        debug!("{}: found {} copies", card.id, copy_count_curr);
+        acc += copy_count_curr;
+
+        // Find next copies.
+        let match_count = card.find_winning_in_hand();
+        debug!("{}: matched {}", card.id, match_count);
+        for i in 1..match_count + 1 {
+            let card_id_copy = card.id + i;
+            let copy_count = card_copies.entry(card_id_copy).or_insert(0);
+            *copy_count += 1 + copy_count_curr;
+            trace!(
+                "{}: copying {} (count={})",
+                card.id,
+                card_id_copy,
+                copy_count
+            )
+        }
+    }
+    acc
+}
+
+pub const EXAMPLES: &[Example] = &[Example {
+    path: concat!(env!("CARGO_MANIFEST_DIR"), "/example.txt"),
+    part_1: Some("13"),
+    part_2: Some("30"),
+}];
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, part: PuzzlePart, reader: &mut dyn BufRead) -> Result<Solver> {
+        let cards = read_cards(reader)?;
+        Ok(match part {
+            PuzzlePart::One => Box::new(move || solve_1(&cards).map(Answer::from)),
+            PuzzlePart::Two => Box::new(move || Ok(solve_2(&cards).into())),
+        })
+    }
+}
+
+struct Card {
+    id: usize,
+    winning: HashSet<u16>,
+    hand: HashSet<u16>,
+}
+
+impl Card {
+    fn compute_score(&self) -> Result<i64> {
+        let match_count: usize = self.find_winning_in_hand();
+        if match_count == 0 {
+            return Ok(0);
+        }
+        u32::try_from(match_count - 1)
+            .ok()
+            .and_then(|exponent| 2_i64.checked_pow(exponent))
+            .ok_or_else(|| {
+                Error::validation(format!(
+                    "Card {} has {match_count} matches, whose score overflows.",
+                    self.id
+                ))
+            })
+    }
+
+    fn find_winning_in_hand(&self) -> usize {
+        self.hand.intersection(&self.winning).count()
+    }
+}
+
+fn iter_cards<R: BufRead>(reader: &mut R) -> impl Iterator<Item = Result<Card>> + '_ {
+    let card_re = regex!(r"Card\s+(\d+):(.*)");
+    reader.lines().map(|line| -> Result<Card> {
+        let line = line?;
+        let cap = card_re
+            .captures(&line)
+            .ok_or_else(|| Error::parse(format!("Expected a card. Got '{line}'.")))?;
+
+        let card_id: usize = parse_number(&cap[1])?;
+
+        let rest = &cap[2];
+        let (winning, hand) = rest
+            .split_once('|')
+            .ok_or_else(|| Error::parse(format!("No | delimiter in '{line}'")))?;
+
+        let winning = parse_space_sep_numbers::<u16>(winning)?
+            .into_iter()
+            .collect();
+        let hand = parse_space_sep_numbers::<u16>(hand)?.into_iter().collect();
+        let card = Card {
+            id: card_id,
+            winning,
+            hand,
+        };
+        Ok(card)
+    })
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_Card {
+    use super::*;
+
+    #[test]
+    fn test_compute_score() {
+        let id = 1;
+        let winning = HashSet::from([1, 2, 3, 4]);
+        let cards = [
+            // Zero winn
+            Card {
+                id,
+                winning: winning.clone(),
+                hand: [100, 101].into(),
+            },
+            Card {
+                id,
+                winning: winning.clone(),
+                hand: [1, 100, 101].into(),
+            },
+            Card {
+                id,
+                winning: winning.clone(),
+                hand: [1, 2, 999].into(),
+            },
+            Card {
+                id,
+                winning: winning.clone(),
+                hand: [1, 2, 3, 4, 999, 777].into(),
+            },
+        ];
+
+        let scores: Vec<_> = cards.iter().map(|c| c.compute_score().unwrap()).collect();
+        assert_eq!(scores, [0, 1, 2, 8]);
+    }
+
+    #[test]
+    fn test_score_overflow() {
+        let card = |count: u16| Card {
+            id: 7,
+            winning: (0..count).collect(),
+            hand: (0..count).collect(),
+        };
+        assert_eq!(card(63).compute_score().unwrap(), 1 << 62);
+        let err = card(70).compute_score().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Card 7 has 70 matches, whose score overflows."
+        );
+        assert!(solve_1(&[card(63), card(63)]).is_err());
+    }
+}
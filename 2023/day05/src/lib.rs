@@ -0,0 +1,612 @@
+use itertools::Itertools;
+use log::debug;
+use rayon::prelude::*;
+use std::cmp;
+use std::io::BufRead;
+
+use aoc::progress::Progress;
+use aoc::{
+    parse_space_sep_numbers, regex, Answer, Error, Example, Lines, PuzzlePart, Result, Solution,
+    Solver,
+};
+
+pub fn run_1<R: BufRead>(reader: R, route: Route) -> Result<Id> {
+    solve_1(&Almanac::parse(reader)?, route)
+}
+
+pub fn run_2<R: BufRead>(reader: R, route: Route, brute_force: bool) -> Result<Id> {
+    let almanac = Almanac::parse(reader)?;
+    let seed_ranges = almanac.seed_ranges()?;
+    solve_2(&seed_ranges, &almanac, route, brute_force)
+}
+
+/// Maps each seed number along `route`, and returns the lowest result.
+fn solve_1(almanac: &Almanac, route: Route) -> Result<Id> {
+    let maps = almanac.maps_between(route)?;
+    let lowest = almanac
+        .seeds()
+        .iter()
+        .map(|&seed_id| {
+            let id = maps
+                .iter()
+                .fold(seed_id, |id_curr, map| map.lookup(id_curr));
+            debug!("{} {seed_id} -> {} {id}", route.src, route.dst);
+            id
+        })
+        .min();
+    lowest.ok_or_else(|| Error::validation("No location!"))
+}
+
+fn solve_2(
+    seed_ranges: &[SeedRange],
+    almanac: &Almanac,
+    route: Route,
+    brute_force: bool,
+) -> Result<Id> {
+    let maps = almanac.maps_between(route)?;
+    let location_id = if brute_force {
+        par_find_lowest_location(seed_ranges, maps)
+    } else {
+        find_lowest_location(seed_ranges, maps)
+    };
+    let location_id = location_id.ok_or_else(|| Error::validation("No location!"))?;
+    Ok(location_id)
+}
+
+/// The categories to map seed numbers from and to, e.g. `soil` to `humidity`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Route<'a> {
+    pub src: &'a str,
+    pub dst: &'a str,
+}
+
+impl Route<'_> {
+    /// What the puzzle asks for.
+    pub const SEED_TO_LOCATION: Route<'static> = Route {
+        src: "seed",
+        dst: "location",
+    };
+}
+
+/// The seed numbers, and the maps from each category to the next.
+///
+/// The maps must form a single chain from `seed` to `location`, which may be listed in any
+/// order: each category other than `location` must be mapped to exactly one other, without
+/// coming back to an earlier one, and every map must be on the way.
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<Id>,
+    /// In the order of the chain, from `seed` to `location`.
+    maps: Vec<Map>,
+}
+
+impl Almanac {
+    pub fn parse<R: BufRead>(reader: R) -> Result<Self> {
+        let mut lines = Lines::new(reader);
+        let seeds = read_seed_numbers(&mut lines)?;
+        let mut maps = Vec::new();
+        while let Some(map) = read_map(&mut lines)? {
+            maps.push(map);
+        }
+        Self::new(seeds, maps)
+    }
+
+    /// Orders `maps` into a chain from `seed` to `location`, or fails if they don't form one.
+    fn new(seeds: Vec<Id>, mut maps: Vec<Map>) -> Result<Self> {
+        let Route { src, dst } = Route::SEED_TO_LOCATION;
+        let mut chain: Vec<Map> = Vec::with_capacity(maps.len());
+        let mut category = src.to_string();
+        while category != dst {
+            let next: Vec<usize> = (0..maps.len())
+                .filter(|&i| maps[i].src == category)
+                .collect();
+            let i = match next[..] {
+                [i] => i,
+                [] => {
+                    return Err(Error::validation(format!(
+                        "Expected a map from '{category}' on the way to '{dst}'. Got none."
+                    )))
+                }
+                _ => {
+                    return Err(Error::validation(format!(
+                        "Expected one map from '{category}'. Got {}.",
+                        next.len()
+                    )))
+                }
+            };
+            let map = maps.swap_remove(i);
+            if map.dst == map.src
+                || map.dst == src
+                || chain.iter().any(|other| other.src == map.dst)
+            {
+                return Err(Error::validation(format!(
+                    "Map '{}-to-{}' leads back to an earlier category.",
+                    map.src, map.dst
+                )));
+            }
+            category = map.dst.clone();
+            chain.push(map);
+        }
+        if let Some(map) = maps.first() {
+            return Err(Error::validation(format!(
+                "Map '{}-to-{}' isn't on the way from '{src}' to '{dst}'.",
+                map.src, map.dst
+            )));
+        }
+        Ok(Self { seeds, maps: chain })
+    }
+
+    pub fn seeds(&self) -> &[Id] {
+        &self.seeds
+    }
+
+    /// Every category, from `seed` to `location`.
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        let first = self.maps.first().map(|map| map.src.as_str());
+        first
+            .into_iter()
+            .chain(self.maps.iter().map(|map| map.dst.as_str()))
+    }
+
+    /// Maps `id` of category `route.src` to its number in category `route.dst`.
+    pub fn lookup(&self, route: Route, id: Id) -> Result<Id> {
+        let maps = self.maps_between(route)?;
+        Ok(maps.iter().fold(id, |id_curr, map| map.lookup(id_curr)))
+    }
+
+    /// The seed numbers read as pairs of start and length, as in part two.
+    fn seed_ranges(&self) -> Result<Vec<SeedRange>> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(Error::parse(format!(
+                "Expected pairs of seed numbers. Got {} numbers.",
+                self.seeds.len()
+            ))
+            .at_line(1));
+        }
+        self.seeds
+            .iter()
+            .tuples()
+            .map(|(&start, &len)| {
+                check_range_end(start, len).map_err(|err| err.at_line(1))?;
+                Ok(SeedRange { start, len })
+            })
+            .collect()
+    }
+
+    /// The maps to go through, in order, to get from `route.src` to `route.dst`. Empty if
+    /// they're the same category.
+    fn maps_between(&self, route: Route) -> Result<&[Map]> {
+        let Route { src, dst } = route;
+        let position = |category: &str| self.categories().position(|other| other == category);
+        let unknown = |category: &str| {
+            Error::validation(format!(
+                "Unknown category '{category}'. Expected one of: {}.",
+                self.categories().join(", ")
+            ))
+        };
+        let start = position(src).ok_or_else(|| unknown(src))?;
+        let end = position(dst).ok_or_else(|| unknown(dst))?;
+        if end < start {
+            return Err(Error::validation(format!(
+                "Cannot map '{src}' to '{dst}': it comes earlier."
+            )));
+        }
+        Ok(&self.maps[start..end])
+    }
+}
+
+pub const EXAMPLES: &[Example] = &[Example {
+    path: concat!(env!("CARGO_MANIFEST_DIR"), "/example.txt"),
+    part_1: Some("35"),
+    part_2: Some("46"),
+}];
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, part: PuzzlePart, reader: &mut dyn BufRead) -> Result<Solver> {
+        let almanac = Almanac::parse(reader)?;
+        let route = Route::SEED_TO_LOCATION;
+        Ok(match part {
+            PuzzlePart::One => Box::new(move || solve_1(&almanac, route).map(Answer::from)),
+            PuzzlePart::Two => {
+                let seed_ranges = almanac.seed_ranges()?;
+                Box::new(move || solve_2(&seed_ranges, &almanac, route, false).map(Answer::from))
+            }
+        })
+    }
+}
+
+/// Maps all seed ranges through all of `maps` at once, and returns the lowest result.
+fn find_lowest_location(seed_ranges: &[SeedRange], maps: &[Map]) -> Option<Id> {
+    let location_ranges = maps
+        .iter()
+        .fold(seed_ranges.to_vec(), |ranges, map| map.map_ranges(&ranges));
+    location_ranges.iter().map(|range| range.start).min()
+}
+
+/// Looks up every seed individually, in parallel on rayon's thread pool.
+fn par_find_lowest_location(seed_ranges: &[SeedRange], maps: &[Map]) -> Option<Id> {
+    /// Seeds per unit of work. Workers only report their progress once per chunk, so that they
+    /// rarely contend on the shared counter.
+    const CHUNK_LEN: Id = 1 << 16;
+
+    let seed_count: Id = seed_ranges.iter().map(|r| r.len).sum();
+    let progress = Progress::new(seed_count as u64);
+
+    let chunks: Vec<SeedRange> = seed_ranges
+        .iter()
+        .flat_map(|range| {
+            (0..range.len)
+                .step_by(CHUNK_LEN as usize)
+                .map(move |offset| SeedRange {
+                    start: range.start + offset,
+                    len: cmp::min(CHUNK_LEN, range.len - offset),
+                })
+        })
+        .collect();
+    let best_location = chunks
+        .into_par_iter()
+        .filter_map(|chunk| {
+            let best_location = (chunk.start..chunk.start + chunk.len)
+                .map(|seed_id| {
+                    maps.iter()
+                        .fold(seed_id, |id_curr, map| map.lookup(id_curr))
+                })
+                .min();
+
+            progress.inc(chunk.len as u64);
+            best_location
+        })
+        .min();
+    progress.finish();
+    best_location
+}
+
+pub type Id = i64;
+
+/// Reads the `seeds: 79 14 55 13` line.
+fn read_seed_numbers<R: BufRead>(lines: &mut Lines<R>) -> Result<Vec<Id>> {
+    let (lineno, line) = lines
+        .next()
+        .ok_or_else(|| Error::parse("Unexpected EOF").at_line(1))??;
+    let seeds = line.strip_prefix("seeds:").ok_or_else(|| {
+        Error::parse(format!(
+            "Expected line to start with 'seeds: '. Got '{line}'."
+        ))
+        .at_line(lineno)
+    })?;
+    parse_space_sep_numbers(seeds).map_err(|err| err.at_line(lineno))
+}
+
+/// Fails if the range starting at `start` overflows `Id` before its end.
+fn check_range_end(start: Id, len: Id) -> Result<()> {
+    match start.checked_add(len) {
+        Some(_) => Ok(()),
+        None => Err(Error::parse(format!(
+            "Expected a range ending below {}. Got start {start} and length {len}.",
+            Id::MAX
+        ))),
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct SeedRange {
+    start: Id,
+    len: Id,
+}
+
+fn read_map<R: BufRead>(lines: &mut Lines<R>) -> Result<Option<Map>> {
+    let (header_lineno, header) = loop {
+        match lines.next() {
+            Some(line) => {
+                let (lineno, line) = line?;
+                if !line.is_empty() {
+                    break (lineno, line);
+                }
+            }
+            None => return Ok(None),
+        }
+    };
+    let header_re = regex!(r"(\w+)-to-(\w+) map");
+    let caps = header_re.captures(&header).ok_or_else(|| {
+        Error::parse(format!("Invalid map header line: '{header}'")).at_line(header_lineno)
+    })?;
+
+    let src = caps[1].to_string();
+    let dst = caps[2].to_string();
+
+    let mut ranges: Vec<MapRange> = Vec::new();
+    for line in lines.by_ref() {
+        let (lineno, line) = line?;
+        if line.is_empty() {
+            break;
+        }
+        let numbers: Vec<Id> = parse_space_sep_numbers(&line).map_err(|err| err.at_line(lineno))?;
+        let [dst_start, src_start, len] = numbers[..] else {
+            return Err(Error::parse(format!(
+                "Expected exactly 3 numbers. Got {}: '{line}'",
+                numbers.len(),
+            ))
+            .at_line(lineno));
+        };
+        check_range_end(dst_start, len)
+            .and_then(|_| check_range_end(src_start, len))
+            .map_err(|err| err.at_line(lineno))?;
+        ranges.push(MapRange {
+            dst_start,
+            src_start,
+            len,
+        });
+    }
+
+    Ok(Some(Map { src, dst, ranges }))
+}
+
+#[derive(Debug)]
+struct Map {
+    pub src: String,
+    pub dst: String,
+    pub ranges: Vec<MapRange>,
+}
+
+impl Map {
+    fn lookup(&self, src_id: Id) -> Id {
+        for range in &self.ranges {
+            if src_id >= range.src_start {
+                let delta = src_id - range.src_start;
+                if delta < range.len {
+                    return range.dst_start + delta;
+                }
+            }
+        }
+        src_id
+    }
+
+    /// Same as [`Map::lookup`], but for entire ranges of IDs.
+    /// Ranges which straddle the boundaries of a [`MapRange`] are split up, so the output
+    /// may contain more ranges than the input.
+    fn map_ranges(&self, src_ranges: &[SeedRange]) -> Vec<SeedRange> {
+        let mut mapped: Vec<SeedRange> = Vec::new();
+        // Ranges not yet matched by any `MapRange`.
+        // Like `lookup`, the first matching `MapRange` wins.
+        let mut pending: Vec<SeedRange> = src_ranges.to_vec();
+        for range in &self.ranges {
+            let range_end = range.src_start + range.len;
+            let mut unmatched: Vec<SeedRange> = Vec::new();
+            for src in pending {
+                let src_end = src.start + src.len;
+                // Part before the map range.
+                let before_end = cmp::min(src_end, range.src_start);
+                if src.start < before_end {
+                    unmatched.push(SeedRange {
+                        start: src.start,
+                        len: before_end - src.start,
+                    });
+                }
+                // Part overlapping the map range.
+                let overlap_start = cmp::max(src.start, range.src_start);
+                let overlap_end = cmp::min(src_end, range_end);
+                if overlap_start < overlap_end {
+                    mapped.push(SeedRange {
+                        start: range.dst_start + (overlap_start - range.src_start),
+                        len: overlap_end - overlap_start,
+                    });
+                }
+                // Part after the map range.
+                let after_start = cmp::max(src.start, range_end);
+                if after_start < src_end {
+                    unmatched.push(SeedRange {
+                        start: after_start,
+                        len: src_end - after_start,
+                    });
+                }
+            }
+            pending = unmatched;
+        }
+        // Anything left over maps to itself.
+        mapped.extend(pending);
+        mapped
+    }
+}
+
+#[derive(Debug)]
+struct MapRange {
+    dst_start: Id,
+    src_start: Id,
+    len: Id,
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod test_Map {
+    use std::collections::HashMap;
+    use std::io::BufReader;
+
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let txt = ["seed-to-soil map:", "50 98 2", "52 50 48"].join("\n");
+        let mut lines = Lines::new(BufReader::new(txt.as_bytes()));
+        let map = read_map(&mut lines).unwrap().unwrap();
+
+        let matrix: HashMap<Id, Id> = HashMap::from([
+            // src, dst
+            (0, 0),
+            (1, 1),
+            (48, 48),
+            (50, 52),
+            (51, 53),
+            (96, 98),
+            (97, 99),
+            (98, 50),
+            (99, 51),
+        ]);
+        let actual: HashMap<Id, Id> = HashMap::from_iter(
+            matrix
+                .keys()
+                .map(|seed_id| (*seed_id, map.lookup(*seed_id))),
+        );
+
+        assert_eq!(actual, matrix);
+    }
+
+    #[test]
+    fn test_map_ranges() {
+        let txt = ["seed-to-soil map:", "50 98 2", "52 50 48"].join("\n");
+        let mut lines = Lines::new(BufReader::new(txt.as_bytes()));
+        let map = read_map(&mut lines).unwrap().unwrap();
+
+        let src_ranges = [
+            // Straddles both map ranges, and the unmapped IDs below them.
+            SeedRange { start: 40, len: 62 },
+            // Entirely unmapped.
+            SeedRange { start: 0, len: 5 },
+        ];
+        let mut actual = map.map_ranges(&src_ranges);
+        actual.sort();
+        assert_eq!(
+            actual,
+            vec![
+                SeedRange { start: 0, len: 5 },
+                SeedRange { start: 40, len: 10 },
+                SeedRange { start: 50, len: 2 },
+                SeedRange { start: 52, len: 48 },
+                SeedRange { start: 100, len: 2 },
+            ]
+        );
+
+        // Should agree with looking up every ID individually.
+        let mut expected: Vec<Id> = src_ranges
+            .iter()
+            .flat_map(|r| r.start..r.start + r.len)
+            .map(|id| map.lookup(id))
+            .collect();
+        expected.sort();
+        let mut actual: Vec<Id> = actual
+            .iter()
+            .flat_map(|r| r.start..r.start + r.len)
+            .collect();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod test_Almanac {
+    use super::*;
+
+    fn parse(maps: &[&str]) -> Result<Almanac> {
+        let txt = format!("seeds: 1 2\n\n{}", maps.join("\n\n"));
+        Almanac::parse(txt.as_bytes())
+    }
+
+    #[test]
+    fn test_any_order() {
+        let almanac =
+            parse(&["soil-to-location map:\n0 10 5", "seed-to-soil map:\n10 0 5"]).unwrap();
+        assert_eq!(
+            almanac.categories().collect::<Vec<_>>(),
+            ["seed", "soil", "location"]
+        );
+        assert_eq!(almanac.lookup(Route::SEED_TO_LOCATION, 3).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_gap() {
+        let err = parse(&["seed-to-soil map:", "fertilizer-to-location map:"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expected a map from 'soil' on the way to 'location'. Got none."
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let err = parse(&[
+            "seed-to-soil map:",
+            "soil-to-water map:",
+            "water-to-soil map:",
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Map 'water-to-soil' leads back to an earlier category."
+        );
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let err = parse(&["seed-to-soil map:", "soil-to-soil map:"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Map 'soil-to-soil' leads back to an earlier category."
+        );
+    }
+
+    #[test]
+    fn test_unreachable_map() {
+        let err = parse(&["seed-to-location map:", "water-to-light map:"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Map 'water-to-light' isn't on the way from 'seed' to 'location'."
+        );
+    }
+
+    #[test]
+    fn test_ambiguous() {
+        let err = parse(&["seed-to-location map:", "seed-to-soil map:"]).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+    }
+
+    #[test]
+    fn test_overflowing_ranges() {
+        let err = parse(&["seed-to-location map:\n0 9223372036854775800 100"]).unwrap_err();
+        assert!(matches!(err, Error::Parse { line: Some(4), .. }));
+
+        let txt = "seeds: 9223372036854775800 100\n\nseed-to-location map:\n0 0 5";
+        let almanac = Almanac::parse(txt.as_bytes()).unwrap();
+        let err = almanac.seed_ranges().unwrap_err();
+        assert!(matches!(err, Error::Parse { line: Some(1), .. }));
+    }
+
+    #[test]
+    fn test_routes() {
+        let almanac = Almanac::parse(aoc::open_input(EXAMPLES[0].path).unwrap()).unwrap();
+        let route = |src, dst| Route { src, dst };
+        // Soil 81 is fertilizer 81, water 81, light 74, temperature 78, and humidity 78.
+        assert_eq!(almanac.lookup(route("soil", "humidity"), 81).unwrap(), 78);
+        assert_eq!(almanac.lookup(route("light", "light"), 5).unwrap(), 5);
+        assert!(almanac.lookup(route("humidity", "soil"), 5).is_err());
+        assert!(almanac.lookup(route("seed", "nowhere"), 5).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_run_2 {
+    use super::*;
+    use aoc::open_input;
+
+    #[test]
+    fn test_brute_force_agrees() {
+        let example = EXAMPLES[0].path;
+        assert_eq!(
+            run_2(open_input(example).unwrap(), Route::SEED_TO_LOCATION, false).unwrap(),
+            run_2(open_input(example).unwrap(), Route::SEED_TO_LOCATION, true).unwrap()
+        );
+    }
+}
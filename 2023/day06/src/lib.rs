@@ -0,0 +1,258 @@
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+use aoc::{
+    parse_number, parse_space_sep_numbers, Error, Example, Lines, PuzzlePart, Result, Solution,
+    Solver,
+};
+
+pub fn run_1<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(solve_1(&parse_1(reader)?))
+}
+
+pub fn run_2<R: BufRead>(reader: R) -> Result<usize> {
+    Ok(count_winning_holds(&parse_2(reader)?))
+}
+
+fn parse_1<R: BufRead>(reader: R) -> Result<Vec<Race>> {
+    let mut lines = Lines::new(reader);
+
+    let times = read_line(&mut lines, "Time:", parse_line1)?;
+    let records = read_line(&mut lines, "Distance:", parse_line1)?;
+    if times.len() != records.len() {
+        return Err(Error::parse(format!(
+            "Expected as many records as times. Got {} and {}.",
+            records.len(),
+            times.len()
+        ))
+        .at_line(lines.lineno()));
+    }
+    let races = times
+        .iter()
+        .zip(records.iter())
+        .map(|(time, record)| Race {
+            time: *time,
+            record: *record,
+        })
+        .collect();
+    Ok(races)
+}
+
+/// Part two has a single race, whose numbers are spread over the whole line.
+fn parse_2<R: BufRead>(reader: R) -> Result<Race> {
+    let mut lines = Lines::new(reader);
+
+    let time = read_line(&mut lines, "Time:", parse_line2)?;
+    let record = read_line(&mut lines, "Distance:", parse_line2)?;
+    Ok(Race { time, record })
+}
+
+fn solve_1(races: &[Race]) -> usize {
+    let mut acc = 1;
+    for race in races {
+        acc *= count_winning_holds(race);
+    }
+    acc
+}
+
+pub const EXAMPLES: &[Example] = &[Example {
+    path: concat!(env!("CARGO_MANIFEST_DIR"), "/example.txt"),
+    part_1: Some("288"),
+    part_2: Some("71503"),
+}];
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, part: PuzzlePart, reader: &mut dyn BufRead) -> Result<Solver> {
+        Ok(match part {
+            PuzzlePart::One => {
+                let races = parse_1(reader)?;
+                Box::new(move || Ok(solve_1(&races).into()))
+            }
+            PuzzlePart::Two => {
+                let race = parse_2(reader)?;
+                Box::new(move || Ok(count_winning_holds(&race).into()))
+            }
+        })
+    }
+}
+
+/// Reads the next line, which must start with `prefix`, and parses the rest of it.
+fn read_line<R: BufRead, T>(
+    lines: &mut Lines<R>,
+    prefix: &str,
+    parse: fn(&str) -> Result<T>,
+) -> Result<T> {
+    let (lineno, line) = lines
+        .next()
+        .ok_or_else(|| Error::parse("Unexpected EOF").at_line(lines.lineno() + 1))??;
+    let rest = line.strip_prefix(prefix).ok_or_else(|| {
+        Error::parse(format!(
+            "Expected line to start with '{prefix}'. Got '{line}'."
+        ))
+        .at_line(lineno)
+    })?;
+    parse(rest).map_err(|err| err.at_line(lineno))
+}
+
+fn parse_line1(s: &str) -> Result<Vec<i64>> {
+    parse_space_sep_numbers(s)
+}
+
+fn parse_line2(s: &str) -> Result<i64> {
+    parse_number(&s.replace(' ', ""))
+}
+
+struct Race {
+    pub time: i64,
+    pub record: i64,
+}
+
+/// Largest `r` with `r * r <= n`.
+fn isqrt(n: i128) -> i128 {
+    // The float estimate is only off by a little; correct it exactly.
+    let mut r = (n as f64).sqrt() as i128;
+    while r * r > n {
+        r -= 1;
+    }
+    while (r + 1) * (r + 1) <= n {
+        r += 1;
+    }
+    r
+}
+
+/// Hold times that beat the record, i.e. with `hold * (time - hold) > record`. The button has to
+/// be held, and released before the race ends, so holds are between 1 and `time - 1`.
+fn winning_holds(race: &Race) -> Option<RangeInclusive<i64>> {
+    let time = race.time as i128;
+    let record = race.record as i128;
+    let beats = |hold: i128| hold * (time - hold) > record;
+
+    // The distance is symmetric around its peak at `time / 2`.
+    let peak = time / 2;
+    if time < 2 || !beats(peak) {
+        return None;
+    }
+    // Start from the lower root of `hold^2 - time * hold + record = 0`, which is real since the
+    // peak beats the record, then step onto the first winning hold.
+    let discriminant = time * time - 4 * record;
+    let mut lo = ((time - isqrt(discriminant)) / 2).clamp(1, peak);
+    while !beats(lo) {
+        lo += 1;
+    }
+    while lo > 1 && beats(lo - 1) {
+        lo -= 1;
+    }
+    Some(lo as i64..=(time - lo) as i64)
+}
+
+fn count_winning_holds(race: &Race) -> usize {
+    match winning_holds(race) {
+        Some(holds) => (holds.end() - holds.start() + 1) as usize,
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod test_winning_holds {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn calc_beat_record(race: &Race) -> Vec<i64> {
+        let mut ret = Vec::new();
+        for button_hold_time in 1..race.time {
+            let velocity = button_hold_time;
+            let time_left = race.time - button_hold_time;
+            let distance = velocity * time_left;
+            if distance > race.record {
+                ret.push(button_hold_time);
+            }
+        }
+        ret
+    }
+
+    #[test]
+    fn test_example() {
+        let holds = winning_holds(&Race { time: 7, record: 9 });
+        assert_eq!(holds, Some(2..=5));
+        let holds = winning_holds(&Race {
+            time: 30,
+            record: 200,
+        });
+        assert_eq!(holds, Some(11..=19));
+    }
+
+    #[test]
+    fn test_exact_record() {
+        // 5 * 5 == 25 only ties the record.
+        assert_eq!(
+            winning_holds(&Race {
+                time: 10,
+                record: 25
+            }),
+            None
+        );
+        assert_eq!(
+            winning_holds(&Race {
+                time: 10,
+                record: 24
+            }),
+            Some(5..=5)
+        );
+    }
+
+    #[test]
+    fn test_negative_record() {
+        // Even not moving at all would beat it, but the button has to be held and released.
+        assert_eq!(
+            winning_holds(&Race {
+                time: 7,
+                record: -5
+            }),
+            Some(1..=6)
+        );
+        assert_eq!(
+            winning_holds(&Race {
+                time: 1,
+                record: -5
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_large() {
+        let race = Race {
+            time: 53_897_698,
+            record: 313_109_012_141_201,
+        };
+        assert_eq!(winning_holds(&race), Some(6_623_214..=47_274_484));
+        assert_eq!(count_winning_holds(&race), 40_651_271);
+    }
+
+    proptest! {
+        #[test]
+        fn test_agrees_with_brute_force(
+            (time, record) in (-10..2_000_i64)
+                .prop_flat_map(|time| (Just(time), -1_000..=time * time / 4 + 1))
+        ) {
+            let race = Race { time, record };
+            let expected = calc_beat_record(&race);
+            let holds = winning_holds(&race);
+            prop_assert_eq!(holds.map_or(vec![], |holds| holds.collect()), expected);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+// Until the placeholders below are filled in.
+#![allow(unused_variables)]
+
+use std::io::BufRead;
+
+use aoc::{Answer, Example, PuzzlePart, Result, Solution, Solver};
+
+pub fn run_1<R: BufRead>(reader: R) -> Result<u64> {
+    solve_1(&parse_input(reader)?)
+}
+
+pub fn run_2<R: BufRead>(reader: R) -> Result<u64> {
+    solve_2(&parse_input(reader)?)
+}
+
+struct Input {}
+
+fn parse_input<R: BufRead>(reader: R) -> Result<Input> {
+    // =============
+    // NEW CODE HERE
+    // =============
+
+    Ok(Input {})
+}
+
+fn solve_1(input: &Input) -> Result<u64> {
+    Ok(0)
+}
+
+fn solve_2(input: &Input) -> Result<u64> {
+    Ok(0)
+}
+
+pub const EXAMPLES: &[Example] = &[Example {
+    path: concat!(env!("CARGO_MANIFEST_DIR"), "/example.txt"),
+    part_1: None,
+    part_2: None,
+}];
+
+pub struct DayNN;
+
+impl Solution for DayNN {
+    fn year(&self) -> u16 {
+        0
+    }
+
+    fn day(&self) -> u8 {
+        0
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn parse(&self, part: PuzzlePart, reader: &mut dyn BufRead) -> Result<Solver> {
+        let input = parse_input(reader)?;
+        Ok(match part {
+            PuzzlePart::One => Box::new(move || solve_1(&input).map(Answer::from)),
+            PuzzlePart::Two => Box::new(move || solve_2(&input).map(Answer::from)),
+        })
+    }
+}
@@ -0,0 +1,22 @@
+use std::process::ExitCode;
+
+use aoc::{exit_code, init_logging, open_input, report_answer, Args, PuzzlePart, Result};
+use clap::Parser;
+use dayNN::{run_1, run_2, DayNN};
+
+fn run(args: Args) -> Result<()> {
+    for (path, expected) in args.inputs(&DayNN)? {
+        let answer = match args.part {
+            PuzzlePart::One => run_1(open_input(&path)?),
+            PuzzlePart::Two => run_2(open_input(&path)?),
+        }?;
+        report_answer(&answer.into(), expected.as_deref())?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    init_logging(args.verbose);
+    exit_code(run(args))
+}
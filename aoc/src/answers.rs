@@ -0,0 +1,88 @@
+//! Known-correct answers, recorded in each day's `answers.txt`.
+//!
+//! Each non-empty line that isn't a `#` comment holds `<input> <part> <answer>`, e.g.
+//! `example.txt 1 142`. Input paths are relative to the file itself.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+
+use crate::{Error, PuzzlePart, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedAnswer {
+    pub input: PathBuf,
+    pub part: PuzzlePart,
+    pub answer: String,
+}
+
+pub fn parse_answers(s: &str) -> Result<Vec<ExpectedAnswer>> {
+    let mut ret = Vec::new();
+    for (line_idx, line) in s.lines().enumerate() {
+        let lineno = line_idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_ascii_whitespace().collect();
+        let [input, part, answer] = fields[..] else {
+            return Err(
+                Error::parse(format!("Expected '<input> <part> <answer>'. Got '{line}'."))
+                    .at_line(lineno),
+            );
+        };
+        let part = PuzzlePart::from_str(part, true)
+            .map_err(|err| Error::parse(format!("Invalid part: {err}")).at_line(lineno))?;
+        ret.push(ExpectedAnswer {
+            input: PathBuf::from(input),
+            part,
+            answer: answer.to_string(),
+        });
+    }
+    Ok(ret)
+}
+
+/// Reads answers from `path`, resolving their inputs relative to its directory.
+pub fn read_answers(path: &Path) -> Result<Vec<ExpectedAnswer>> {
+    let s = fs::read_to_string(path).map_err(|err| Error::io(path.to_string_lossy(), err))?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let answers = parse_answers(&s)?;
+    Ok(answers
+        .into_iter()
+        .map(|expected| ExpectedAnswer {
+            input: dir.join(expected.input),
+            ..expected
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test_parse_answers {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let s = "# comment\n\nexample.txt 1 142\ninput.txt two abc\n";
+        assert_eq!(
+            parse_answers(s).unwrap(),
+            vec![
+                ExpectedAnswer {
+                    input: PathBuf::from("example.txt"),
+                    part: PuzzlePart::One,
+                    answer: "142".to_string(),
+                },
+                ExpectedAnswer {
+                    input: PathBuf::from("input.txt"),
+                    part: PuzzlePart::Two,
+                    answer: "abc".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(parse_answers("example.txt 1").is_err());
+        assert!(parse_answers("example.txt 3 142").is_err());
+    }
+}
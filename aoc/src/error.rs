@@ -0,0 +1,166 @@
+//! Error type shared by every day.
+use std::error::Error as StdError;
+use std::{fmt, io};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Failed to open, read or write a file, e.g. an input.
+    Io {
+        /// Not known when e.g. reading lines from an arbitrary reader.
+        path: Option<String>,
+        source: io::Error,
+    },
+    /// The input doesn't have the expected format.
+    Parse {
+        /// 1-based.
+        line: Option<usize>,
+        /// 1-based, in characters.
+        column: Option<usize>,
+        message: String,
+        source: Option<Box<dyn StdError + Send + Sync>>,
+    },
+    /// The input is well-formed, but doesn't make sense, e.g. an almanac which doesn't end up
+    /// at 'location'.
+    Validation(String),
+    /// A request to the puzzle website failed, e.g. to download an input.
+    Http {
+        url: String,
+        source: Box<dyn StdError + Send + Sync>,
+    },
+}
+
+impl Error {
+    pub fn io(path: impl Into<String>, source: io::Error) -> Self {
+        Error::Io {
+            path: Some(path.into()),
+            source,
+        }
+    }
+
+    /// Use [`Error::at_line`] and friends to fill in the details.
+    pub fn parse(message: impl Into<String>) -> Self {
+        Error::Parse {
+            line: None,
+            column: None,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Error::Validation(message.into())
+    }
+
+    pub fn http(url: impl Into<String>, source: impl StdError + Send + Sync + 'static) -> Self {
+        Error::Http {
+            url: url.into(),
+            source: Box::new(source),
+        }
+    }
+
+    /// Sets the line number of a parse error, unless it's already known.
+    /// This lets line-agnostic parsers be called from line-aware ones.
+    pub fn at_line(mut self, lineno: usize) -> Self {
+        if let Error::Parse { line, .. } = &mut self {
+            line.get_or_insert(lineno);
+        }
+        self
+    }
+
+    /// Sets the column number of a parse error, unless it's already known.
+    pub fn at_column(mut self, colno: usize) -> Self {
+        if let Error::Parse { column, .. } = &mut self {
+            column.get_or_insert(colno);
+        }
+        self
+    }
+
+    pub fn with_source(mut self, err: impl StdError + Send + Sync + 'static) -> Self {
+        if let Error::Parse { source, .. } = &mut self {
+            *source = Some(Box::new(err));
+        }
+        self
+    }
+
+    /// Formats the error along with its chain of sources, one per line.
+    pub fn report(&self) -> String {
+        let mut ret = self.to_string();
+        let mut source = self.source();
+        while let Some(err) = source {
+            ret.push_str(&format!("\n  Caused by: {err}"));
+            source = err.source();
+        }
+        ret
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io {
+                path: Some(path), ..
+            } => write!(f, "Failed to access '{path}'"),
+            Error::Io { path: None, .. } => write!(f, "Failed to read input"),
+            Error::Parse {
+                line,
+                column,
+                message,
+                ..
+            } => match (line, column) {
+                (Some(line), Some(column)) => write!(f, "Line {line}, column {column}: {message}"),
+                (Some(line), None) => write!(f, "Line {line}: {message}"),
+                (None, Some(column)) => write!(f, "Column {column}: {message}"),
+                (None, None) => write!(f, "{message}"),
+            },
+            Error::Validation(message) => write!(f, "{message}"),
+            Error::Http { url, .. } => write!(f, "Request to '{url}' failed"),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse { source, .. } => source
+                .as_ref()
+                .map(|err| err.as_ref() as &(dyn StdError + 'static)),
+            Error::Validation(_) => None,
+            Error::Http { source, .. } => Some(source.as_ref()),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Error::Io { path: None, source }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_Error {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = Error::parse("not a number: 'x'").at_line(3).at_column(7);
+        assert_eq!(err.to_string(), "Line 3, column 7: not a number: 'x'");
+
+        // Inner location wins.
+        let err = Error::parse("oops").at_line(2).at_line(5);
+        assert_eq!(err.to_string(), "Line 2: oops");
+    }
+
+    #[test]
+    fn test_report() {
+        let source = "x".parse::<u8>().unwrap_err();
+        let err = Error::parse("not a number").at_line(1).with_source(source);
+        assert_eq!(
+            err.report(),
+            "Line 1: not a number\n  Caused by: invalid digit found in string"
+        );
+    }
+}
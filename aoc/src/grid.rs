@@ -0,0 +1,328 @@
+//! A rectangular grid of cells, usually parsed from a matrix of characters.
+//!
+//! `x` is the column and `y` the row, counting from the top-left corner.
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::{Error, Lines, Result};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns `None` if the result would be negative.
+    pub fn checked_add(self, offset: Offset) -> Option<Coord> {
+        Some(Coord {
+            x: self.x.checked_add_signed(offset.dx)?,
+            y: self.y.checked_add_signed(offset.dy)?,
+        })
+    }
+}
+
+/// A step from one cell to another, e.g. to a neighbor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Offset {
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Offset {
+    pub const UP: Offset = Offset::new(0, -1);
+    pub const DOWN: Offset = Offset::new(0, 1);
+    pub const LEFT: Offset = Offset::new(-1, 0);
+    pub const RIGHT: Offset = Offset::new(1, 0);
+    pub const UP_LEFT: Offset = Offset::new(-1, -1);
+    pub const UP_RIGHT: Offset = Offset::new(1, -1);
+    pub const DOWN_LEFT: Offset = Offset::new(-1, 1);
+    pub const DOWN_RIGHT: Offset = Offset::new(1, 1);
+
+    /// Horizontal and vertical neighbors.
+    pub const ORTHOGONAL: [Offset; 4] = [Offset::UP, Offset::RIGHT, Offset::DOWN, Offset::LEFT];
+    /// Orthogonal and diagonal neighbors, clockwise from the top-left.
+    pub const ALL: [Offset; 8] = [
+        Offset::UP_LEFT,
+        Offset::UP,
+        Offset::UP_RIGHT,
+        Offset::RIGHT,
+        Offset::DOWN_RIGHT,
+        Offset::DOWN,
+        Offset::DOWN_LEFT,
+        Offset::LEFT,
+    ];
+
+    pub const fn new(dx: isize, dy: isize) -> Self {
+        Self { dx, dy }
+    }
+}
+
+/// Cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Fails if the rows don't all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(Error::parse(format!(
+                    "Expected a row of width {width}. Got {}.",
+                    row.len()
+                ))
+                .at_line(y + 1));
+            }
+            cells.extend(row);
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Reads lines up to the first empty one or EOF, parsing each character into a cell. The
+    /// following lines are left in `lines` for the caller.
+    pub fn read<R: BufRead>(
+        lines: &mut Lines<R>,
+        mut parse_cell: impl FnMut(char) -> Result<T>,
+    ) -> Result<Self> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for line in lines.by_ref() {
+            let (lineno, line) = line?;
+            if line.is_empty() {
+                break;
+            }
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(x, c)| parse_cell(c).map_err(|err| err.at_line(lineno).at_column(x + 1)))
+                .collect::<Result<Vec<T>>>()?;
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    return Err(Error::parse(format!(
+                        "Expected a row of width {}. Got '{line}'.",
+                        first.len()
+                    ))
+                    .at_line(lineno));
+                }
+            }
+            rows.push(row);
+        }
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.x < self.width && coord.y < self.height
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.contains(coord)
+            .then(|| &self.cells[coord.y * self.width + coord.x])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        if self.contains(coord) {
+            Some(&mut self.cells[coord.y * self.width + coord.x])
+        } else {
+            None
+        }
+    }
+
+    /// Every coordinate, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Coord { x, y }))
+    }
+
+    /// Every cell with its coordinate, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(&self.cells)
+    }
+
+    /// Panics if `y` is outside the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is outside the grid");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, and an empty grid has no rows anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Panics if `x` is outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// Neighbors of `coord` that are within the grid, for each of `offsets`.
+    pub fn neighbors<'a>(
+        &'a self,
+        coord: Coord,
+        offsets: &'a [Offset],
+    ) -> impl Iterator<Item = Coord> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&offset| coord.checked_add(offset))
+            .filter(|&neighbor| self.contains(neighbor))
+    }
+
+    /// Horizontal and vertical neighbors of `coord` within the grid.
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        self.neighbors(coord, &Offset::ORTHOGONAL)
+    }
+
+    /// Horizontal, vertical and diagonal neighbors of `coord` within the grid.
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        self.neighbors(coord, &Offset::ALL)
+    }
+
+    /// Walks from `start` (included) in steps of `offset` until leaving the grid. Use e.g.
+    /// `Offset::DOWN_RIGHT` for a diagonal.
+    pub fn scan(&self, start: Coord, offset: Offset) -> impl Iterator<Item = Coord> + '_ {
+        std::iter::successors(Some(start), move |&coord| coord.checked_add(offset))
+            .take_while(|&coord| self.contains(coord))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is outside the grid"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::read(&mut Lines::new(s.as_bytes()), Ok)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_Grid {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\nghi\n".parse().unwrap()
+    }
+
+    fn values(grid: &Grid<char>, coords: impl Iterator<Item = Coord>) -> String {
+        coords.map(|coord| grid[coord]).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[Coord::new(2, 1)], 'f');
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.row(2), ['g', 'h', 'i']);
+        assert_eq!(grid.column(1).collect::<String>(), "beh");
+    }
+
+    #[test]
+    #[should_panic(expected = "row 3 is outside the grid")]
+    fn test_row_out_of_range() {
+        grid().row(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside the grid")]
+    fn test_column_out_of_range() {
+        let _ = grid().column(3);
+    }
+
+    #[test]
+    fn test_parse_ragged() {
+        let err = "abc\nde\n".parse::<Grid<char>>().unwrap_err();
+        assert!(matches!(err, Error::Parse { line: Some(2), .. }));
+    }
+
+    #[test]
+    fn test_read_stops_at_empty_line() {
+        let mut lines = Lines::new("ab\ncd\n\nrest\n".as_bytes());
+        let grid = Grid::read(&mut lines, Ok).unwrap();
+        assert_eq!(grid.height(), 2);
+        assert_eq!(lines.next().unwrap().unwrap(), (4, "rest".to_string()));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid();
+        assert_eq!(values(&grid, grid.neighbors4(Coord::new(1, 1))), "bfhd");
+        assert_eq!(values(&grid, grid.neighbors8(Coord::new(1, 1))), "abcfihgd");
+        assert_eq!(values(&grid, grid.neighbors4(Coord::new(0, 0))), "bd");
+        assert_eq!(values(&grid, grid.neighbors8(Coord::new(2, 2))), "efh");
+    }
+
+    #[test]
+    fn test_scan() {
+        let grid = grid();
+        assert_eq!(
+            values(&grid, grid.scan(Coord::new(0, 0), Offset::DOWN_RIGHT)),
+            "aei"
+        );
+        assert_eq!(
+            values(&grid, grid.scan(Coord::new(0, 2), Offset::UP_RIGHT)),
+            "gec"
+        );
+        assert_eq!(
+            values(&grid, grid.scan(Coord::new(1, 0), Offset::LEFT)),
+            "ba"
+        );
+    }
+}
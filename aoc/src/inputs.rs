@@ -0,0 +1,141 @@
+//! Puzzle inputs, cached on disk and downloaded from the puzzle website when missing.
+//!
+//! Inputs differ per user, so downloading one needs the `session` cookie of a logged-in browser.
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{DayId, Error, Result};
+
+/// The site serves inputs at `<base-url>/<year>/day/<day>/input`.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Where inputs are cached, and how to download missing ones.
+#[derive(clap::Args, Debug, Clone)]
+pub struct InputStore {
+    /// Directory where inputs are cached, as `<year>/<NN>.txt`.
+    #[arg(
+        long,
+        env = "AOC_INPUTS_DIR",
+        default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs")
+    )]
+    pub inputs_dir: PathBuf,
+    /// Puzzle website to download missing inputs from.
+    #[arg(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    pub base_url: String,
+    /// Value of the `session` cookie of a logged-in browser.
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    pub session: Option<String>,
+    /// How the site can reach whoever runs this, e.g. a repository URL or an email address.
+    /// Sent along with every request.
+    #[arg(long, env = "AOC_CONTACT")]
+    pub contact: Option<String>,
+}
+
+impl InputStore {
+    /// Path of the cached input, whether it exists or not.
+    pub fn path(&self, id: DayId) -> PathBuf {
+        self.inputs_dir.join(format!("{id}.txt"))
+    }
+
+    /// Returns the path of the cached input, downloading it first if it isn't cached yet.
+    pub fn get(&self, id: DayId) -> Result<String> {
+        let path = self.path(id);
+        let path_str = path.to_string_lossy().into_owned();
+        if path.exists() {
+            return Ok(path_str);
+        }
+
+        log::info!("Downloading the input of {id} to '{path_str}'");
+        let input = self.fetch(id)?;
+        // Written aside first, so that an interrupted download doesn't leave a truncated input.
+        let partial = path.with_extension("txt.part");
+        let write = || {
+            fs::create_dir_all(path.parent().expect("the path has a year directory"))?;
+            fs::write(&partial, &input)?;
+            fs::rename(&partial, &path)
+        };
+        write().map_err(|err| Error::io(&path_str, err))?;
+        Ok(path_str)
+    }
+
+    /// Downloads the input, without caching it.
+    pub fn fetch(&self, id: DayId) -> Result<String> {
+        let session = self.session()?;
+        let url = format!(
+            "{}/{}/day/{}/input",
+            self.base_url.trim_end_matches('/'),
+            id.year,
+            id.day
+        );
+        ureq::get(&url)
+            .set("Cookie", &format!("session={session}"))
+            .set("User-Agent", &self.user_agent())
+            .call()
+            .map_err(|err| Error::http(&url, err))?
+            .into_string()
+            .map_err(|err| Error::http(&url, err))
+    }
+
+    pub(crate) fn session(&self) -> Result<&str> {
+        self.session.as_deref().ok_or_else(|| {
+            Error::validation("No session cookie. Set AOC_SESSION or pass --session.")
+        })
+    }
+
+    /// The site asks automated tools to identify themselves, and to say who to contact.
+    pub(crate) fn user_agent(&self) -> String {
+        let tool = concat!("aoc/", env!("CARGO_PKG_VERSION"));
+        match &self.contact {
+            Some(contact) => format!("{tool} (+{contact})"),
+            None => tool.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_InputStore {
+    use super::*;
+    use crate::test_server::{store, TestServer};
+
+    #[test]
+    fn test_download_then_cache() {
+        let server = TestServer::start(vec![(200, "1 2 3\n")]);
+        let (store, _dir) = store("cache", &server.url(), Some("abc"));
+
+        let path = store.get(DayId::new(2023, 5)).unwrap();
+        assert!(path.ends_with("2023/05.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2 3\n");
+
+        // Cached, so no second request.
+        assert_eq!(store.get(DayId::new(2023, 5)).unwrap(), path);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2023/day/5/input");
+        assert_eq!(requests[0].header("cookie"), Some("session=abc"));
+        assert!(requests[0]
+            .header("user-agent")
+            .unwrap()
+            .ends_with("(+https://example.com/me)"));
+        assert_eq!(requests[0].body, "");
+    }
+
+    #[test]
+    fn test_no_session() {
+        let server = TestServer::start(vec![]);
+        let (store, _dir) = store("no-session", &server.url(), None);
+        let err = store.get(DayId::new(2023, 1)).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn test_not_found() {
+        let server = TestServer::start(vec![(404, "Not found")]);
+        let (store, _dir) = store("not-found", &server.url(), Some("abc"));
+        let err = store.get(DayId::new(2023, 25)).unwrap_err();
+        assert!(matches!(err, Error::Http { .. }));
+        assert!(!store.path(DayId::new(2023, 25)).exists());
+    }
+}
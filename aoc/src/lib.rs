@@ -0,0 +1,386 @@
+//! Utilities shared by every day.
+use std::io::{self, BufRead, BufReader};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::{fmt, fs};
+
+use clap::{Parser, ValueEnum};
+
+pub mod answers;
+mod error;
+pub mod grid;
+pub mod inputs;
+pub mod progress;
+pub mod submit;
+#[cfg(test)]
+mod test_server;
+
+pub use error::{Error, Result};
+use inputs::InputStore;
+
+// Re-exported for use by `regex!`, so that days don't need to depend on these themselves.
+#[doc(hidden)]
+pub use once_cell;
+#[doc(hidden)]
+pub use regex as regex_crate;
+
+/// Compiles a regex once, and caches it for subsequent calls.
+// https://github.com/matklad/once_cell/blob/master/examples/regex.rs
+#[macro_export]
+macro_rules! regex {
+    ($re:literal $(,)?) => {{
+        static RE: $crate::once_cell::sync::OnceCell<$crate::regex_crate::Regex> =
+            $crate::once_cell::sync::OnceCell::new();
+        RE.get_or_init(|| $crate::regex_crate::Regex::new($re).unwrap())
+    }};
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum PuzzlePart {
+    #[value(alias = "1")]
+    One,
+    #[value(alias = "2")]
+    Two,
+}
+
+impl PuzzlePart {
+    pub const ALL: [PuzzlePart; 2] = [PuzzlePart::One, PuzzlePart::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            PuzzlePart::One => 1,
+            PuzzlePart::Two => 2,
+        }
+    }
+}
+
+/// A puzzle answer, as returned by solutions.
+/// Printing is left to the caller.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => n.fmt(f),
+            Answer::Str(s) => s.fmt(f),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Int(n.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_int!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Int(n as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Str(s.to_string())
+    }
+}
+
+/// A puzzle of a given year, written e.g. `2023/05`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DayId {
+    pub year: u16,
+    /// Day of the month, from 1 to 25.
+    pub day: u8,
+}
+
+impl DayId {
+    pub fn new(year: u16, day: u8) -> Self {
+        Self { year, day }
+    }
+}
+
+impl fmt::Display for DayId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{:02}", self.year, self.day)
+    }
+}
+
+impl FromStr for DayId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let Some((year, day)) = s.split_once('/') else {
+            return Err(Error::parse(format!(
+                "Expected '<year>/<day>', e.g. '2023/05'. Got '{s}'."
+            )));
+        };
+        let year: u16 = parse_number(year)?;
+        let day: u8 = parse_number(day)?;
+        if year < 2015 || !(1..=25).contains(&day) {
+            return Err(Error::parse(format!(
+                "Expected a year from 2015 and a day from 1 to 25. Got '{s}'."
+            )));
+        }
+        Ok(Self { year, day })
+    }
+}
+
+/// A sample input from the puzzle text, along with its known answers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// Typically `concat!(env!("CARGO_MANIFEST_DIR"), "/example.txt")`, so that it can be
+    /// found regardless of the working directory.
+    pub path: &'static str,
+    /// Not every example applies to both parts.
+    pub part_1: Option<&'static str>,
+    pub part_2: Option<&'static str>,
+}
+
+impl Example {
+    pub fn expected(&self, part: PuzzlePart) -> Option<&'static str> {
+        match part {
+            PuzzlePart::One => self.part_1,
+            PuzzlePart::Two => self.part_2,
+        }
+    }
+}
+
+/// Solves a part of the puzzle, given the input that was parsed for it.
+pub type Solver = Box<dyn FnOnce() -> Result<Answer>>;
+
+/// A day's solution, so that days can be run generically, e.g. by the `aoc` runner.
+pub trait Solution {
+    fn year(&self) -> u16;
+
+    /// Day of the month, from 1 to 25.
+    fn day(&self) -> u8;
+
+    fn id(&self) -> DayId {
+        DayId::new(self.year(), self.day())
+    }
+
+    fn examples(&self) -> &'static [Example];
+
+    /// Parses the input for `part`, and returns what's left to do to solve it. The two phases are
+    /// separate so that they can be timed separately.
+    fn parse(&self, part: PuzzlePart, reader: &mut dyn BufRead) -> Result<Solver>;
+
+    fn run(&self, part: PuzzlePart, path: &str) -> Result<Answer> {
+        let solve = self.parse(part, &mut open_input(path)?)?;
+        solve()
+    }
+}
+
+/// Command-line arguments common to every day.
+/// Days that need more options can `#[command(flatten)]` this into their own struct.
+#[derive(Parser, Debug)]
+#[command()]
+pub struct Args {
+    /// Path to the puzzle input. Reads stdin if `-` or omitted.
+    pub path: Option<String>,
+    /// Use this day's cached puzzle input, downloading it first if missing.
+    #[arg(long, conflicts_with_all = ["path", "example"])]
+    pub fetch: bool,
+    #[arg(short, long, value_enum)]
+    pub part: PuzzlePart,
+    /// Run the examples from the puzzle text instead, and check their answers.
+    #[arg(long, conflicts_with = "path")]
+    pub example: bool,
+    /// Log details to stderr. Repeat for more, e.g. `-vv`.
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+    #[command(flatten)]
+    pub store: InputStore,
+}
+
+impl Args {
+    /// Returns the input paths to run, along with their expected answers if known.
+    pub fn inputs(&self, solution: &dyn Solution) -> Result<Vec<(String, Option<String>)>> {
+        if self.fetch {
+            return Ok(vec![(self.store.get(solution.id())?, None)]);
+        }
+        if !self.example {
+            let path = self.path.as_deref().unwrap_or(STDIN_PATH);
+            return Ok(vec![(path.to_string(), None)]);
+        }
+        let inputs: Vec<_> = solution
+            .examples()
+            .iter()
+            .filter_map(|example| {
+                let expected = example.expected(self.part)?;
+                Some((example.path.to_string(), Some(expected.to_string())))
+            })
+            .collect();
+        if inputs.is_empty() {
+            return Err(Error::validation(format!(
+                "No example for part {}.",
+                self.part.number()
+            )));
+        }
+        Ok(inputs)
+    }
+}
+
+/// Prints `answer`, and fails if it doesn't match the `expected` one.
+pub fn report_answer(answer: &Answer, expected: Option<&str>) -> Result<()> {
+    println!("Answer: {answer}");
+    match expected {
+        Some(expected) if expected != answer.to_string() => Err(Error::validation(format!(
+            "Wrong answer! Expected {expected}."
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Sends log messages to stderr, at a level depending on how many `-v` flags were given.
+/// `RUST_LOG` still takes precedence, e.g. `RUST_LOG=aoc2023_day05=trace`.
+pub fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => log::LevelFilter::Warn,
+        1 => log::LevelFilter::Info,
+        2 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .format_timestamp(None)
+        .parse_default_env()
+        .init();
+}
+
+/// Turns the result of a binary's main logic into an exit code, printing the error if any.
+pub fn exit_code(result: Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {}", err.report());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Path that stands for stdin.
+pub const STDIN_PATH: &str = "-";
+
+/// Opens the puzzle input at `path` for buffered reading, or stdin if `path` is `-`.
+pub fn open_input(path: &str) -> Result<Box<dyn BufRead>> {
+    if path == STDIN_PATH {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = fs::File::open(path).map_err(|err| Error::io(path, err))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+/// Parses a single number, keeping the offending text in the error message.
+pub fn parse_number<T>(s: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    s.parse::<T>()
+        .map_err(|err| Error::parse(format!("Not a number: '{s}'")).with_source(err))
+}
+
+/// Parses whitespace-separated numbers, e.g. `"79 14 55 13"`.
+pub fn parse_space_sep_numbers<T>(s: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    s.split_ascii_whitespace().map(parse_number).collect()
+}
+
+/// Like [`BufRead::lines`], but also yields 1-based line numbers, for error messages.
+/// Useful when several functions take turns reading from the same input.
+pub struct Lines<R> {
+    inner: io::Lines<R>,
+    lineno: usize,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            inner: reader.lines(),
+            lineno: 0,
+        }
+    }
+
+    /// Line number of the last line read, or 0 if none were.
+    pub fn lineno(&self) -> usize {
+        self.lineno
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = Result<(usize, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.inner.next()?;
+        self.lineno += 1;
+        Some(line.map(|line| (self.lineno, line)).map_err(Error::from))
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_Answer {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(42_u64).to_string(), "42");
+        assert_eq!(Answer::from(-7_i64).to_string(), "-7");
+        assert_eq!(Answer::from(3_usize), Answer::Int(3));
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_DayId {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let id: DayId = "2023/5".parse().unwrap();
+        assert_eq!(id, DayId::new(2023, 5));
+        assert_eq!(id.to_string(), "2023/05");
+        assert!("2023".parse::<DayId>().is_err());
+        assert!("2023/26".parse::<DayId>().is_err());
+        assert!("23/05".parse::<DayId>().is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_parse_space_sep_numbers {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        assert_eq!(
+            parse_space_sep_numbers::<u8>(" 1  2 3 ").unwrap(),
+            [1, 2, 3]
+        );
+
+        let err = parse_space_sep_numbers::<u8>("1 x2 3").unwrap_err();
+        assert_eq!(err.to_string(), "Not a number: 'x2'");
+    }
+}
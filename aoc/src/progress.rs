@@ -0,0 +1,165 @@
+//! Progress of long-running solutions, drawn as a single line that updates in place.
+//!
+//! The line is only drawn when both stdout and stderr are terminals, so that piped or redirected
+//! output never contains it. It's drawn on stderr, and cleared once done.
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often to redraw at most.
+const REDRAW_EVERY: Duration = Duration::from_millis(100);
+
+/// Counts work done out of a known total. Can be shared between threads.
+pub struct Progress {
+    total: u64,
+    done: AtomicU64,
+    start: Instant,
+    /// Milliseconds since `start` after which to redraw next.
+    next_draw_ms: AtomicU64,
+    visible: bool,
+    finished: AtomicBool,
+}
+
+impl Progress {
+    pub fn new(total: u64) -> Self {
+        let visible = io::stdout().is_terminal() && io::stderr().is_terminal();
+        Self::with_visibility(total, visible)
+    }
+
+    /// Never draws anything.
+    pub fn hidden(total: u64) -> Self {
+        Self::with_visibility(total, false)
+    }
+
+    fn with_visibility(total: u64, visible: bool) -> Self {
+        Self {
+            total,
+            done: AtomicU64::new(0),
+            start: Instant::now(),
+            next_draw_ms: AtomicU64::new(0),
+            visible,
+            finished: AtomicBool::new(false),
+        }
+    }
+
+    pub fn done(&self) -> u64 {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    /// Records `n` more units of work done, and redraws if it's been a while.
+    pub fn inc(&self, n: u64) {
+        let done = self.done.fetch_add(n, Ordering::Relaxed) + n;
+        if !self.visible {
+            return;
+        }
+        let elapsed = self.start.elapsed();
+        let elapsed_ms = elapsed.as_millis() as u64;
+        let next_draw_ms = self.next_draw_ms.load(Ordering::Relaxed);
+        if elapsed_ms < next_draw_ms {
+            return;
+        }
+        // Only one of the threads that get here at the same time draws.
+        let next = elapsed_ms + REDRAW_EVERY.as_millis() as u64;
+        if self
+            .next_draw_ms
+            .compare_exchange(next_draw_ms, next, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            let line = format_line(done, self.total, elapsed);
+            let _ = write!(io::stderr(), "\r\x1b[2K{line}");
+        }
+    }
+
+    /// Clears the line. Also happens on drop.
+    pub fn finish(&self) {
+        if self.visible && !self.finished.swap(true, Ordering::Relaxed) {
+            let _ = write!(io::stderr(), "\r\x1b[2K");
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// E.g. `[10.0s] 1.50M / 3.00M (50 %) 150.00k/s ETA 10s`.
+fn format_line(done: u64, total: u64, elapsed: Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+    let rate = if secs > 0.0 { done as f64 / secs } else { 0.0 };
+    let percent = if total > 0 {
+        done as f64 / total as f64 * 100.0
+    } else {
+        100.0
+    };
+    let eta = if rate > 0.0 {
+        format_duration(Duration::from_secs_f64(
+            total.saturating_sub(done) as f64 / rate,
+        ))
+    } else {
+        "?".to_string()
+    };
+    format!(
+        "[{secs:.1}s] {} / {} ({percent:.0} %) {}/s ETA {eta}",
+        format_count(done as f64),
+        format_count(total as f64),
+        format_count(rate),
+    )
+}
+
+/// Abbreviates large counts, e.g. `1.50M`.
+fn format_count(n: f64) -> String {
+    for (threshold, suffix) in [(1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "k")] {
+        if n >= threshold {
+            return format!("{:.2}{suffix}", n / threshold);
+        }
+    }
+    format!("{n:.0}")
+}
+
+/// E.g. `1h02m`, `3m05s` or `42s`.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{h}h{m:02}m")
+    } else if m > 0 {
+        format!("{m}m{s:02}s")
+    } else {
+        format!("{s}s")
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_Progress {
+    use super::*;
+
+    #[test]
+    fn test_inc() {
+        let progress = Progress::hidden(10);
+        progress.inc(3);
+        progress.inc(4);
+        assert_eq!(progress.done(), 7);
+    }
+
+    #[test]
+    fn test_format_line() {
+        assert_eq!(
+            format_line(1_500_000, 3_000_000, Duration::from_secs(10)),
+            "[10.0s] 1.50M / 3.00M (50 %) 150.00k/s ETA 10s"
+        );
+        assert_eq!(
+            format_line(0, 100, Duration::ZERO),
+            "[0.0s] 0 / 100 (0 %) 0/s ETA ?"
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(format_duration(Duration::from_secs(185)), "3m05s");
+        assert_eq!(format_duration(Duration::from_secs(3720)), "1h02m");
+    }
+}
@@ -0,0 +1,385 @@
+//! Submits answers to the puzzle website, and keeps a ledger of past attempts.
+//!
+//! Wrong answers lock out further attempts for a while, so an answer that the ledger already
+//! knows to be wrong is refused before it's sent. That includes numbers outside the bounds set
+//! by past "too high" and "too low" attempts.
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::inputs::InputStore;
+use crate::{parse_number, regex, DayId, Error, PuzzlePart, Result};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint.
+    Wrong,
+    /// Answered too recently. Says nothing about the answer.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part is already solved, or part 1 isn't yet. Says nothing about the answer.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Name in the ledger, for verdicts that say something about the answer.
+    fn name(self) -> Option<&'static str> {
+        match self {
+            Verdict::Correct => Some("correct"),
+            Verdict::TooHigh => Some("too-high"),
+            Verdict::TooLow => Some("too-low"),
+            Verdict::Wrong => Some("wrong"),
+            Verdict::RateLimited { .. } | Verdict::WrongLevel => None,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            Verdict::Correct,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Wrong,
+        ]
+        .into_iter()
+        .find(|verdict| verdict.name() == Some(name))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh => write!(f, "Wrong answer: too high."),
+            Verdict::TooLow => write!(f, "Wrong answer: too low."),
+            Verdict::Wrong => write!(f, "Wrong answer."),
+            Verdict::RateLimited { wait: Some(wait) } => {
+                write!(
+                    f,
+                    "Answered too recently. Try again in {}s.",
+                    wait.as_secs()
+                )
+            }
+            Verdict::RateLimited { wait: None } => write!(f, "Answered too recently."),
+            Verdict::WrongLevel => write!(
+                f,
+                "Not the right part: it's already solved, or the previous one isn't."
+            ),
+        }
+    }
+}
+
+/// Reads the verdict from the page the site answers a submission with.
+pub fn parse_response(body: &str) -> Result<Verdict> {
+    if body.contains("That's the right answer") {
+        return Ok(Verdict::Correct);
+    }
+    if body.contains("You gave an answer too recently") {
+        let wait = regex!(r"You have (?:(\d+)m )?(\d+)s left to wait").captures(body);
+        let wait = match wait {
+            Some(caps) => {
+                let minutes: u64 = caps.get(1).map_or(Ok(0), |m| parse_number(m.as_str()))?;
+                let seconds: u64 = parse_number(&caps[2])?;
+                Some(Duration::from_secs(minutes * 60 + seconds))
+            }
+            None => None,
+        };
+        return Ok(Verdict::RateLimited { wait });
+    }
+    if body.contains("You don't seem to be solving the right level") {
+        return Ok(Verdict::WrongLevel);
+    }
+    if body.contains("That's not the right answer") {
+        return Ok(if body.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if body.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        });
+    }
+    Err(Error::validation(
+        "Expected a verdict in the response. Got none.",
+    ))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: PuzzlePart,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Past attempts, stored one per line as `<day> <part> <answer> <verdict>`, e.g. `5 1 35 too-low`.
+pub struct Ledger {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Ledger {
+    /// Where `store` keeps the ledger of `year`, next to its inputs.
+    pub fn path(store: &InputStore, year: u16) -> PathBuf {
+        store.inputs_dir.join(year.to_string()).join("ledger.txt")
+    }
+
+    /// Reads the ledger at `path`, which is empty if the file doesn't exist yet.
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let attempts = match fs::read_to_string(&path) {
+            Ok(s) => parse_attempts(&s)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(Error::io(path.to_string_lossy(), err)),
+        };
+        Ok(Self { path, attempts })
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Fails if `answer` is already known to be wrong, or if the part is already solved.
+    pub fn check(&self, day: u8, part: PuzzlePart, answer: &str) -> Result<()> {
+        let refuse = |reason: String| {
+            Err(Error::validation(format!(
+                "Not submitting {answer} for day {day} part {}: {reason}",
+                part.number()
+            )))
+        };
+        let number = answer.parse::<i128>().ok();
+        for attempt in self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part)
+        {
+            let known = &attempt.answer;
+            match attempt.verdict {
+                Verdict::Correct if known == answer => {
+                    return refuse("it's already correct.".into())
+                }
+                Verdict::Correct => return refuse(format!("already solved with {known}.")),
+                _ if known == answer => return refuse("it's already known to be wrong.".into()),
+                Verdict::TooHigh => {
+                    if let (Some(number), Ok(high)) = (number, known.parse::<i128>()) {
+                        if number >= high {
+                            return refuse(format!("{known} was already too high."));
+                        }
+                    }
+                }
+                Verdict::TooLow => {
+                    if let (Some(number), Ok(low)) = (number, known.parse::<i128>()) {
+                        if number <= low {
+                            return refuse(format!("{known} was already too low."));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends the attempt to the ledger file, unless the verdict says nothing about the answer.
+    pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+        let Some(name) = attempt.verdict.name() else {
+            return Ok(());
+        };
+        let path = self.path.to_string_lossy().into_owned();
+        let line = format!(
+            "{} {} {} {name}\n",
+            attempt.day,
+            attempt.part.number(),
+            attempt.answer
+        );
+        let append = || {
+            if let Some(dir) = self.path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?
+                .write_all(line.as_bytes())
+        };
+        append().map_err(|err| Error::io(path, err))?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+fn parse_attempts(s: &str) -> Result<Vec<Attempt>> {
+    let mut attempts = Vec::new();
+    for (line_idx, line) in s.lines().enumerate() {
+        let lineno = line_idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_ascii_whitespace().collect();
+        let [day, part, answer, verdict] = fields[..] else {
+            return Err(Error::parse(format!(
+                "Expected '<day> <part> <answer> <verdict>'. Got '{line}'."
+            ))
+            .at_line(lineno));
+        };
+        let part = PuzzlePart::from_str(part, true)
+            .map_err(|err| Error::parse(format!("Invalid part: {err}")).at_line(lineno))?;
+        let verdict = Verdict::from_name(verdict)
+            .ok_or_else(|| Error::parse(format!("Unknown verdict '{verdict}'.")).at_line(lineno))?;
+        attempts.push(Attempt {
+            day: parse_number(day).map_err(|err| err.at_line(lineno))?,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
+    }
+    Ok(attempts)
+}
+
+/// Checks `answer` against the ledger, which must be that of `id`'s year, posts it, and records
+/// the verdict.
+pub fn submit(
+    store: &InputStore,
+    ledger: &mut Ledger,
+    id: DayId,
+    part: PuzzlePart,
+    answer: &str,
+) -> Result<Verdict> {
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(Error::validation(format!(
+            "Expected an answer without spaces. Got '{answer}'."
+        )));
+    }
+    ledger.check(id.day, part, answer)?;
+
+    let session = store.session()?;
+    let url = format!(
+        "{}/{}/day/{}/answer",
+        store.base_url.trim_end_matches('/'),
+        id.year,
+        id.day
+    );
+    let level = part.number().to_string();
+    let body = ureq::post(&url)
+        .set("Cookie", &format!("session={session}"))
+        .set("User-Agent", &store.user_agent())
+        .send_form(&[("level", &level), ("answer", answer)])
+        .map_err(|err| Error::http(&url, err))?
+        .into_string()
+        .map_err(|err| Error::http(&url, err))?;
+    let verdict = parse_response(&body)?;
+    ledger.record(Attempt {
+        day: id.day,
+        part,
+        answer: answer.to_string(),
+        verdict,
+    })?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_submit {
+    use super::*;
+    use crate::test_server::{store, TestServer};
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+        Please wait one minute before trying again.</p></article>";
+    const CORRECT: &str = "<article><p>That's the right answer! You are one gold star closer.";
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(parse_response(CORRECT).unwrap(), Verdict::Correct);
+        assert_eq!(parse_response(TOO_HIGH).unwrap(), Verdict::TooHigh);
+        assert_eq!(
+            parse_response("That's not the right answer; your answer is too low.").unwrap(),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_response("That's not the right answer. If you're stuck...").unwrap(),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            parse_response("You gave an answer too recently. You have 1m 5s left to wait.")
+                .unwrap(),
+            Verdict::RateLimited {
+                wait: Some(Duration::from_secs(65))
+            }
+        );
+        assert_eq!(
+            parse_response("You don't seem to be solving the right level.").unwrap(),
+            Verdict::WrongLevel
+        );
+        assert!(parse_response("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let ledger = Ledger {
+            path: PathBuf::new(),
+            attempts: parse_attempts("5 1 100 too-high\n5 1 10 too-low\n5 1 50 wrong\n").unwrap(),
+        };
+        let part = PuzzlePart::One;
+        assert!(ledger.check(5, part, "100").is_err());
+        assert!(ledger.check(5, part, "150").is_err());
+        assert!(ledger.check(5, part, "10").is_err());
+        assert!(ledger.check(5, part, "50").is_err());
+        assert!(ledger.check(5, part, "42").is_ok());
+        assert!(ledger.check(5, PuzzlePart::Two, "150").is_ok());
+        assert!(ledger.check(6, part, "150").is_ok());
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = TestServer::start(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let (store, _dir) = store("submit", &server.url(), Some("abc"));
+        let path = Ledger::path(&store, 2023);
+        let mut ledger = Ledger::open(path.clone()).unwrap();
+
+        let verdict = submit(
+            &store,
+            &mut ledger,
+            DayId::new(2023, 5),
+            PuzzlePart::Two,
+            "100",
+        )
+        .unwrap();
+        assert_eq!(verdict, Verdict::TooHigh);
+        // Refused without a request.
+        assert!(submit(
+            &store,
+            &mut ledger,
+            DayId::new(2023, 5),
+            PuzzlePart::Two,
+            "120"
+        )
+        .is_err());
+        let verdict = submit(
+            &store,
+            &mut ledger,
+            DayId::new(2023, 5),
+            PuzzlePart::Two,
+            "46",
+        )
+        .unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2023/day/5/answer");
+        assert_eq!(requests[0].header("cookie"), Some("session=abc"));
+        assert_eq!(requests[0].body, "level=2&answer=100");
+
+        // The ledger survives reopening.
+        let ledger = Ledger::open(path).unwrap();
+        assert_eq!(ledger.attempts().len(), 2);
+        assert!(ledger.check(5, PuzzlePart::Two, "46").is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! A stand-in for the puzzle website, so that tests never go online.
+//!
+//! It answers each connection with the next canned response, and records the requests it got.
+//! [`store`] sets up an [`InputStore`] pointing at it, in a scratch directory.
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::InputStore;
+
+/// Deletes the store's directory when dropped, even if the test fails.
+pub struct TempDir(PathBuf);
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// A store in an empty directory named after the test, talking to `base_url`. Keep the guard
+/// alive for as long as the store is used.
+pub fn store(name: &str, base_url: &str, session: Option<&str>) -> (InputStore, TempDir) {
+    let inputs_dir = std::env::temp_dir().join(format!("aoc-test-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&inputs_dir);
+    let store = InputStore {
+        inputs_dir: inputs_dir.clone(),
+        base_url: base_url.to_string(),
+        session: session.map(str::to_string),
+        contact: Some("https://example.com/me".to_string()),
+    };
+    (store, TempDir(inputs_dir))
+}
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// Names are lowercase.
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub struct TestServer {
+    port: u16,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl TestServer {
+    /// Listens on a free local port, answering with `responses` as `(status, body)` in order.
+    /// Connections beyond those are closed without an answer.
+    pub fn start(responses: Vec<(u16, &'static str)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for (stream, (status, body)) in listener.incoming().zip(responses) {
+                let mut stream = stream.unwrap();
+                let request = read_request(&mut stream);
+                recorded.lock().unwrap().push(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Test\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        Self { port, requests }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request(stream: &mut TcpStream) -> Request {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let mut fields = line.split_whitespace();
+    let method = fields.next().unwrap().to_string();
+    let path = fields.next().unwrap().to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        let Some((name, value)) = line.trim_end().split_once(':') else {
+            break;
+        };
+        headers.push((name.to_ascii_lowercase(), value.trim().to_string()));
+    }
+
+    let length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .map_or(0, |(_, value)| value.parse().unwrap());
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+    Request {
+        method,
+        path,
+        headers,
+        body: String::from_utf8(body).unwrap(),
+    }
+}
@@ -0,0 +1,133 @@
+//! Times the parse and solve phases of a day's solution separately, over repeated runs.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc::{Answer, Error, PuzzlePart, Result, Solution};
+
+/// Timings of each timed run of one part on one input.
+pub struct Samples {
+    pub answer: Answer,
+    pub parse: Vec<Duration>,
+    pub solve: Vec<Duration>,
+}
+
+impl Samples {
+    /// Parse and solve time of each run.
+    pub fn total(&self) -> Vec<Duration> {
+        self.parse
+            .iter()
+            .zip(&self.solve)
+            .map(|(parse, solve)| *parse + *solve)
+            .collect()
+    }
+}
+
+/// Runs `part` on `input` `warmup` times untimed, then `samples` times timed. The answer is the
+/// one from the last run.
+pub fn sample(
+    solution: &dyn Solution,
+    part: PuzzlePart,
+    input: &[u8],
+    warmup: usize,
+    samples: usize,
+) -> Result<Samples> {
+    let mut answer = None;
+    for _ in 0..warmup {
+        run_once(solution, part, input)?;
+    }
+    let mut parse = Vec::with_capacity(samples);
+    let mut solve = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        let (sample_answer, parse_time, solve_time) = run_once(solution, part, input)?;
+        answer = Some(sample_answer);
+        parse.push(parse_time);
+        solve.push(solve_time);
+    }
+    let answer = answer.ok_or_else(|| Error::validation("Expected at least one sample."))?;
+    Ok(Samples {
+        answer,
+        parse,
+        solve,
+    })
+}
+
+fn run_once(
+    solution: &dyn Solution,
+    part: PuzzlePart,
+    mut input: &[u8],
+) -> Result<(Answer, Duration, Duration)> {
+    let start = Instant::now();
+    let solve = solution.parse(part, &mut input)?;
+    let parsed = Instant::now();
+    let answer = black_box(solve()?);
+    let solved = Instant::now();
+    Ok((answer, parsed - start, solved - parsed))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Returns `None` if there are no samples.
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        Some(Self {
+            min: *sorted.first()?,
+            median: percentile(&sorted, 50.0),
+            p90: percentile(&sorted, 90.0),
+            p99: percentile(&sorted, 99.0),
+            max: *sorted.last()?,
+        })
+    }
+}
+
+/// Nearest-rank percentile: the smallest sample that at least `p` percent of samples are at most.
+/// `sorted` must be sorted and non-empty.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_Stats {
+    use super::*;
+
+    fn ms(millis: &[u64]) -> Vec<Duration> {
+        millis.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let samples: Vec<u64> = (1..=100).rev().collect();
+        let stats = Stats::new(&ms(&samples)).unwrap();
+        let [min, median, p90, p99, max] = ms(&[1, 50, 90, 99, 100])[..] else {
+            unreachable!()
+        };
+        assert_eq!(
+            stats,
+            Stats {
+                min,
+                median,
+                p90,
+                p99,
+                max
+            }
+        );
+    }
+
+    #[test]
+    fn test_few_samples() {
+        let stats = Stats::new(&ms(&[30, 10, 20])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(20));
+        assert_eq!(stats.p99, Duration::from_millis(30));
+        assert_eq!(Stats::new(&[]), None);
+    }
+}
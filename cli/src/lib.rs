@@ -0,0 +1,30 @@
+//! Registry of every day's solution, of every year.
+use std::path::{Path, PathBuf};
+
+use aoc::{DayId, Solution};
+
+pub mod bench;
+pub mod scaffold;
+
+pub fn solutions() -> Vec<Box<dyn Solution>> {
+    vec![
+        Box::new(aoc2023_day01::Day01),
+        Box::new(aoc2023_day02::Day02),
+        Box::new(aoc2023_day03::Day03),
+        Box::new(aoc2023_day04::Day04),
+        Box::new(aoc2023_day05::Day05),
+        Box::new(aoc2023_day06::Day06),
+    ]
+}
+
+/// Root of the workspace, containing a directory of `dayNN` crates for each year.
+pub fn workspace_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+}
+
+/// Directory of the `<year>/dayNN` crate, holding its examples and `answers.txt`.
+pub fn day_dir(id: DayId) -> PathBuf {
+    workspace_dir()
+        .join(id.year.to_string())
+        .join(format!("day{:02}", id.day))
+}
@@ -0,0 +1,514 @@
+//! Runs any day's solution, e.g. `aoc run 2023/05 --part 2 input.txt`, `aoc run 2023` or
+//! `aoc run all`, times it with `aoc bench`, or submits its answer with `aoc submit 2023/05 -p 2`.
+//! `aoc new-day 2023/07` starts a new day. A bare day such as `5` is of the latest year.
+use std::fmt;
+use std::io::Read;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use aoc::inputs::InputStore;
+use aoc::submit::{self, Ledger, Verdict};
+use aoc::{
+    exit_code, init_logging, open_input, Answer, DayId, Error, PuzzlePart, Result, Solution,
+    STDIN_PATH,
+};
+use aoc_cli::bench::{self, Stats};
+use aoc_cli::{scaffold, solutions, workspace_dir};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser, Debug)]
+#[command()]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Log details to stderr. Repeat for more, e.g. `-vv`.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the solution for one day, or for all days of a year or of every year.
+    Run(InputArgs),
+    /// Time the parse and solve phases of one day's solution, or of several days.
+    Bench(BenchArgs),
+    /// Submit an answer to the puzzle website, unless it's already known to be wrong.
+    Submit(SubmitArgs),
+    /// Create the crate of a new day from `_template`, and add it to the workspace.
+    NewDay {
+        /// Day of the puzzle, e.g. `2024/01`.
+        day: DayArg,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct InputArgs {
+    /// Day to run, e.g. `2023/05` or `5`, a year, e.g. `2023`, or `all`.
+    day: DaySelector,
+    /// Path to the puzzle input, or `-` for stdin. Defaults to the cached input, which is
+    /// downloaded first if missing.
+    path: Option<String>,
+    /// Runs both parts if omitted.
+    #[arg(short, long, value_enum)]
+    part: Option<PuzzlePart>,
+    /// Run the examples from the puzzle text instead, and check their answers.
+    #[arg(long, conflicts_with = "path")]
+    example: bool,
+    #[command(flatten)]
+    store: InputStore,
+}
+
+#[derive(clap::Args, Debug)]
+struct BenchArgs {
+    #[command(flatten)]
+    input: InputArgs,
+    /// Untimed runs before the timed ones.
+    #[arg(long, default_value_t = 3)]
+    warmup: usize,
+    /// Timed runs.
+    #[arg(long, default_value_t = 20, value_parser = clap::value_parser!(u64).range(1..))]
+    samples: u64,
+}
+
+#[derive(clap::Args, Debug)]
+struct SubmitArgs {
+    /// Day of the puzzle, e.g. `2023/05` or `5`.
+    day: DayArg,
+    #[arg(short, long, value_enum)]
+    part: PuzzlePart,
+    /// Defaults to the answer of the day's solution on the cached input.
+    answer: Option<String>,
+    #[command(flatten)]
+    store: InputStore,
+}
+
+/// A day such as `2023/05`, or `5` for the latest year that has it.
+#[derive(Debug, Copy, Clone)]
+struct DayArg {
+    year: Option<u16>,
+    day: u8,
+}
+
+impl DayArg {
+    fn find(self, solutions: &[Box<dyn Solution>]) -> Result<&dyn Solution> {
+        solutions
+            .iter()
+            .filter(|s| s.day() == self.day && self.year.unwrap_or(s.year()) == s.year())
+            .max_by_key(|s| s.year())
+            .map(|s| s.as_ref())
+            .ok_or_else(|| Error::validation(format!("No solution for {self}.")))
+    }
+}
+
+impl fmt::Display for DayArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.year {
+            Some(year) => DayId::new(year, self.day).fmt(f),
+            None => write!(f, "day {}", self.day),
+        }
+    }
+}
+
+impl FromStr for DayArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.contains('/') {
+            let id: DayId = s.parse().map_err(|err: Error| err.to_string())?;
+            return Ok(DayArg {
+                year: Some(id.year),
+                day: id.day,
+            });
+        }
+        match s.parse::<u8>() {
+            Ok(day @ 1..=25) => Ok(DayArg { year: None, day }),
+            _ => Err(format!(
+                "Expected a day such as '5' or '2023/05'. Got '{s}'."
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum DaySelector {
+    All,
+    Year(u16),
+    Day(DayArg),
+}
+
+impl FromStr for DaySelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(DaySelector::All);
+        }
+        match s.parse::<u16>() {
+            Ok(year @ 2015..) => Ok(DaySelector::Year(year)),
+            _ => s.parse().map(DaySelector::Day).map_err(|_| {
+                format!("Expected a day such as '5' or '2023/05', a year, or 'all'. Got '{s}'.")
+            }),
+        }
+    }
+}
+
+/// A part of a day to run on an input.
+struct Job<'a> {
+    solution: &'a dyn Solution,
+    part: PuzzlePart,
+    /// `None` for the cached input, which is only resolved when the job is run, so that a failed
+    /// download fails that day alone.
+    path: Option<String>,
+    expected: Option<&'static str>,
+}
+
+impl Job<'_> {
+    /// Reads the whole input. Stdin can only be read once, so it's kept in `stdin` for the jobs
+    /// after the first one that reads it.
+    fn read_input(&self, store: &InputStore, stdin: &mut Option<Vec<u8>>) -> Result<Vec<u8>> {
+        let path = match &self.path {
+            Some(path) => path.clone(),
+            None => store.get(self.solution.id())?,
+        };
+        if path == STDIN_PATH {
+            if let Some(input) = stdin {
+                return Ok(input.clone());
+            }
+        }
+        let mut input = Vec::new();
+        open_input(&path)?
+            .read_to_end(&mut input)
+            .map_err(|err| Error::io(path.as_str(), err))?;
+        if path == STDIN_PATH {
+            *stdin = Some(input.clone());
+        }
+        Ok(input)
+    }
+}
+
+fn select_jobs<'a>(args: &InputArgs, solutions: &'a [Box<dyn Solution>]) -> Result<Vec<Job<'a>>> {
+    let selected: Vec<&dyn Solution> = match args.day {
+        DaySelector::All | DaySelector::Year(_) if args.path.is_some() => {
+            return Err(Error::validation(
+                "Cannot use a single input path with several days.",
+            ));
+        }
+        DaySelector::All => solutions.iter().map(|s| s.as_ref()).collect(),
+        DaySelector::Year(year) => {
+            let selected: Vec<_> = solutions
+                .iter()
+                .filter(|s| s.year() == year)
+                .map(|s| s.as_ref())
+                .collect();
+            if selected.is_empty() {
+                return Err(Error::validation(format!("No solutions for {year}.")));
+            }
+            selected
+        }
+        DaySelector::Day(day) => vec![day.find(solutions)?],
+    };
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => PuzzlePart::ALL.to_vec(),
+    };
+
+    let mut jobs = Vec::new();
+    for solution in selected {
+        for &part in &parts {
+            if args.example {
+                for example in solution.examples() {
+                    if let Some(expected) = example.expected(part) {
+                        jobs.push(Job {
+                            solution,
+                            part,
+                            path: Some(example.path.to_string()),
+                            expected: Some(expected),
+                        });
+                    }
+                }
+            } else {
+                jobs.push(Job {
+                    solution,
+                    part,
+                    path: args.path.clone(),
+                    expected: None,
+                });
+            }
+        }
+    }
+    Ok(jobs)
+}
+
+struct Outcome {
+    id: DayId,
+    part: PuzzlePart,
+    answer: Result<Answer>,
+    expected: Option<&'static str>,
+    elapsed: Duration,
+}
+
+impl Outcome {
+    fn is_ok(&self) -> bool {
+        match (&self.answer, self.expected) {
+            (Ok(answer), Some(expected)) => answer.to_string() == expected,
+            (Ok(_), None) => true,
+            (Err(_), _) => false,
+        }
+    }
+}
+
+fn run(args: InputArgs) -> Result<Vec<Outcome>> {
+    let solutions = solutions();
+    let mut outcomes = Vec::new();
+    let mut stdin = None;
+    for job in select_jobs(&args, &solutions)? {
+        // Read before starting the clock, so that downloads aren't timed.
+        let input = job.read_input(&args.store, &mut stdin);
+        let start = Instant::now();
+        let answer = input.and_then(|input| {
+            let solve = job.solution.parse(job.part, &mut input.as_slice())?;
+            solve()
+        });
+        let elapsed = start.elapsed();
+        outcomes.push(Outcome {
+            id: job.solution.id(),
+            part: job.part,
+            answer,
+            expected: job.expected,
+            elapsed,
+        });
+    }
+    Ok(outcomes)
+}
+
+fn print_outcomes(outcomes: &[Outcome]) {
+    let rows: Vec<Vec<String>> = outcomes
+        .iter()
+        .map(|outcome| {
+            vec![
+                outcome.id.to_string(),
+                outcome.part.number().to_string(),
+                match &outcome.answer {
+                    Ok(answer) => answer.to_string(),
+                    Err(_) => "ERROR".to_string(),
+                },
+                format!("{:.2?}", outcome.elapsed),
+                match (outcome.is_ok(), outcome.expected) {
+                    (true, Some(_)) => "ok".to_string(),
+                    (false, Some(expected)) => format!("expected {expected}"),
+                    (_, None) => "".to_string(),
+                },
+            ]
+        })
+        .collect();
+    print_table(
+        &["Day", "Part", "Answer", "Time", "Check"],
+        &[
+            Align::Right,
+            Align::Right,
+            Align::Left,
+            Align::Right,
+            Align::Left,
+        ],
+        &rows,
+    );
+}
+
+fn report_outcomes(outcomes: Result<Vec<Outcome>>) -> ExitCode {
+    let outcomes = match outcomes {
+        Ok(outcomes) => outcomes,
+        Err(err) => {
+            eprintln!("Error: {}", err.report());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    print_outcomes(&outcomes);
+    let mut status = ExitCode::SUCCESS;
+    for outcome in &outcomes {
+        if let Err(err) = &outcome.answer {
+            eprintln!(
+                "{} part {} failed: {}",
+                outcome.id,
+                outcome.part.number(),
+                err.report()
+            );
+        }
+        if !outcome.is_ok() {
+            status = ExitCode::FAILURE;
+        }
+    }
+    status
+}
+
+fn bench(args: BenchArgs) -> Result<()> {
+    let solutions = solutions();
+    let jobs = select_jobs(&args.input, &solutions)?;
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut mismatches: Vec<String> = Vec::new();
+    let mut stdin = None;
+    for job in jobs {
+        let input = job.read_input(&args.input.store, &mut stdin)?;
+        let samples = bench::sample(
+            job.solution,
+            job.part,
+            &input,
+            args.warmup,
+            args.samples as usize,
+        )
+        .map_err(|err| {
+            Error::validation(format!(
+                "{} part {} failed: {}",
+                job.solution.id(),
+                job.part.number(),
+                err.report()
+            ))
+        })?;
+        if let Some(expected) = job.expected {
+            if samples.answer.to_string() != expected {
+                mismatches.push(format!(
+                    "{} part {}: expected {expected}, got {}",
+                    job.solution.id(),
+                    job.part.number(),
+                    samples.answer
+                ));
+            }
+        }
+        for (phase, durations) in [
+            ("parse", samples.parse.clone()),
+            ("solve", samples.solve.clone()),
+            ("total", samples.total()),
+        ] {
+            let Some(stats) = Stats::new(&durations) else {
+                continue;
+            };
+            rows.push(vec![
+                job.solution.id().to_string(),
+                job.part.number().to_string(),
+                phase.to_string(),
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.p90),
+                format!("{:.2?}", stats.p99),
+                format!("{:.2?}", stats.max),
+                if phase == "total" {
+                    samples.answer.to_string()
+                } else {
+                    "".to_string()
+                },
+            ]);
+        }
+    }
+    print_table(
+        &[
+            "Day", "Part", "Phase", "Min", "Median", "p90", "p99", "Max", "Answer",
+        ],
+        &[
+            Align::Right,
+            Align::Right,
+            Align::Left,
+            Align::Right,
+            Align::Right,
+            Align::Right,
+            Align::Right,
+            Align::Right,
+            Align::Left,
+        ],
+        &rows,
+    );
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::validation(mismatches.join("\n")))
+    }
+}
+
+fn submit(args: SubmitArgs) -> Result<()> {
+    let solutions = solutions();
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            let solution = args.day.find(&solutions)?;
+            let path = args.store.get(solution.id())?;
+            let answer = solution.run(args.part, &path)?.to_string();
+            println!("Answer: {answer}");
+            answer
+        }
+    };
+    let id = match args.day.year {
+        Some(year) => DayId::new(year, args.day.day),
+        None => args.day.find(&solutions)?.id(),
+    };
+    let mut ledger = Ledger::open(Ledger::path(&args.store, id.year))?;
+    let verdict = submit::submit(&args.store, &mut ledger, id, args.part, &answer)?;
+    println!("{verdict}");
+    match verdict {
+        Verdict::Correct => Ok(()),
+        _ => Err(Error::validation(format!(
+            "Answer {answer} was not accepted."
+        ))),
+    }
+}
+
+fn new_day(day: DayArg) -> Result<()> {
+    let year = match day.year {
+        Some(year) => year,
+        None => solutions()
+            .iter()
+            .map(|s| s.year())
+            .max()
+            .ok_or_else(|| Error::validation("Expected a year, e.g. '2024/01'."))?,
+    };
+    let dir = scaffold::new_day(workspace_dir(), DayId::new(year, day.day))?;
+    let dir = dir.canonicalize().unwrap_or(dir);
+    println!("Created '{}'.", dir.display());
+    println!(
+        "To run it with `aoc`, add `aoc{year}-day{:02}` to the dependencies of `cli` and to \
+        `solutions()`.",
+        day.day
+    );
+    Ok(())
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Align {
+    Left,
+    Right,
+}
+
+/// Prints `rows` under `header`, padding each column to its widest cell.
+fn print_table(header: &[&str], align: &[Align], rows: &[Vec<String>]) {
+    let header: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for row in std::iter::once(&header).chain(rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .zip(align)
+            .map(|((cell, &width), align)| match align {
+                Align::Left => format!("{cell:<width$}"),
+                Align::Right => format!("{cell:>width$}"),
+            })
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_logging(cli.verbose);
+
+    match cli.command {
+        Command::Run(args) => report_outcomes(run(args)),
+        Command::Bench(args) => exit_code(bench(args)),
+        Command::Submit(args) => exit_code(submit(args)),
+        Command::NewDay { day } => exit_code(new_day(day)),
+    }
+}
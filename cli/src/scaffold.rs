@@ -0,0 +1,178 @@
+//! Creates a new day's crate from `_template`, and adds it to the workspace.
+//!
+//! The crate of e.g. `2023/05` is in `2023/day05`, and named `aoc2023-day05` so that days of
+//! different years don't clash.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc::{DayId, Error, Result};
+
+/// Copies `_template` to `<year>/dayNN`, fills in the day, and adds it to the workspace members.
+/// Returns the new crate's directory.
+pub fn new_day(workspace: &Path, id: DayId) -> Result<PathBuf> {
+    let member = format!("{}/day{:02}", id.year, id.day);
+    let dir = workspace.join(&member);
+    if dir.exists() {
+        return Err(Error::validation(format!(
+            "'{}' already exists.",
+            dir.display()
+        )));
+    }
+    let manifest_path = workspace.join("Cargo.toml");
+    let manifest = read(&manifest_path)?;
+    let manifest = add_member(&manifest, &member)?;
+
+    copy_template(&workspace.join("_template"), &dir, id)?;
+    write(&manifest_path, &manifest)?;
+    Ok(dir)
+}
+
+/// Copies every file of `template` to `dir`, filling in the day.
+fn copy_template(template: &Path, dir: &Path, id: DayId) -> Result<()> {
+    fs::create_dir_all(dir).map_err(|err| Error::io(dir.to_string_lossy(), err))?;
+    let entries =
+        fs::read_dir(template).map_err(|err| Error::io(template.to_string_lossy(), err))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| Error::io(template.to_string_lossy(), err))?;
+        let from = entry.path();
+        let to = dir.join(entry.file_name());
+        if from.is_dir() {
+            // Build output, if the template was ever built on its own.
+            if entry.file_name() != "target" {
+                copy_template(&from, &to, id)?;
+            }
+        } else {
+            write(&to, &fill_in(&read(&from)?, id))?;
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the template's placeholders: the package and crate names, `DayNN`, and the year and
+/// day numbers.
+fn fill_in(template: &str, id: DayId) -> String {
+    let DayId { year, day } = id;
+    template
+        .replace("name = \"\"", &format!("name = \"aoc{year}-day{day:02}\""))
+        .replace("dayNN", &format!("aoc{year}_day{day:02}"))
+        .replace("DayNN", &format!("Day{day:02}"))
+        .replace(
+            "fn year(&self) -> u16 {\n        0\n",
+            &format!("fn year(&self) -> u16 {{\n        {year}\n"),
+        )
+        .replace(
+            "fn day(&self) -> u8 {\n        0\n",
+            &format!("fn day(&self) -> u8 {{\n        {day}\n"),
+        )
+}
+
+/// Inserts `member` into the `members` list of a workspace manifest, before the first member
+/// that sorts after it among the days, i.e. those in a year's directory.
+fn add_member(manifest: &str, member: &str) -> Result<String> {
+    let lines: Vec<&str> = manifest.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "members = [")
+        .ok_or_else(|| Error::validation("Expected a 'members = [' line in the manifest."))?;
+    let len = lines[start..]
+        .iter()
+        .position(|line| line.trim() == "]")
+        .ok_or_else(|| Error::validation("Expected the members list to end with ']'."))?;
+    let members = &lines[start + 1..start + len];
+
+    let quoted = format!("\"{member}\",");
+    if members.iter().any(|line| line.trim() == quoted) {
+        return Err(Error::validation(format!(
+            "'{member}' is already a workspace member."
+        )));
+    }
+    let offset = members
+        .iter()
+        .position(|line| {
+            let existing = line.trim().trim_end_matches(',').trim_matches('"');
+            existing.contains('/') && existing > member
+        })
+        .unwrap_or(members.len());
+    let new_line = format!("    {quoted}");
+
+    let mut ret: Vec<&str> = lines.clone();
+    ret.insert(start + 1 + offset, &new_line);
+    Ok(ret.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|err| Error::io(path.to_string_lossy(), err))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).map_err(|err| Error::io(path.to_string_lossy(), err))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_new_day {
+    use super::*;
+
+    const MANIFEST: &str =
+        "[workspace]\nmembers = [\n    \"aoc\",\n    \"2023/day01\",\n    \"2023/day03\",\n]\n";
+
+    #[test]
+    fn test_add_member() {
+        assert_eq!(
+            add_member(MANIFEST, "2023/day02").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"2023/day01\",\n    \"2023/day02\",\n    \"2023/day03\",\n]\n"
+        );
+        assert_eq!(
+            add_member(MANIFEST, "2022/day10").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"2022/day10\",\n    \"2023/day01\",\n    \"2023/day03\",\n]\n"
+        );
+        assert_eq!(
+            add_member(MANIFEST, "2024/day01").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"2023/day01\",\n    \"2023/day03\",\n    \"2024/day01\",\n]\n"
+        );
+        assert!(add_member(MANIFEST, "2023/day03").is_err());
+        assert!(add_member("[workspace]\n", "2023/day02").is_err());
+    }
+
+    #[test]
+    fn test_new_day() {
+        let workspace =
+            std::env::temp_dir().join(format!("aoc-test-new-day-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&workspace);
+        let template = workspace.join("_template");
+        fs::create_dir_all(template.join("src")).unwrap();
+        fs::write(workspace.join("Cargo.toml"), MANIFEST).unwrap();
+        fs::write(template.join("Cargo.toml"), "[package]\nname = \"\"\n").unwrap();
+        fs::write(
+            template.join("src/lib.rs"),
+            "use dayNN::DayNN;\nfn year(&self) -> u16 {\n        0\n    }\nfn day(&self) -> u8 {\n        0\n    }\n",
+        )
+        .unwrap();
+        fs::write(template.join("example.txt"), "").unwrap();
+
+        let id = DayId::new(2023, 2);
+        let dir = new_day(&workspace, id).unwrap();
+        assert_eq!(dir, workspace.join("2023/day02"));
+        assert_eq!(
+            fs::read_to_string(dir.join("Cargo.toml")).unwrap(),
+            "[package]\nname = \"aoc2023-day02\"\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("src/lib.rs")).unwrap(),
+            "use aoc2023_day02::Day02;\nfn year(&self) -> u16 {\n        2023\n    }\nfn day(&self) -> u8 {\n        2\n    }\n"
+        );
+        assert_eq!(fs::read_to_string(dir.join("example.txt")).unwrap(), "");
+        assert!(fs::read_to_string(workspace.join("Cargo.toml"))
+            .unwrap()
+            .contains("\"2023/day01\",\n    \"2023/day02\",\n    \"2023/day03\","));
+
+        // Never overwrites.
+        fs::write(dir.join("example.txt"), "solved").unwrap();
+        assert!(new_day(&workspace, id).is_err());
+        assert_eq!(
+            fs::read_to_string(dir.join("example.txt")).unwrap(),
+            "solved"
+        );
+        fs::remove_dir_all(&workspace).unwrap();
+    }
+}
@@ -0,0 +1,66 @@
+//! Runs every day's solution against its examples, and against the answers to real inputs
+//! recorded in its `answers.txt`.
+use aoc::{PuzzlePart, Solution};
+use aoc_cli::{day_dir, solutions};
+
+/// Returns a description of the failure, if any.
+fn check(solution: &dyn Solution, part: PuzzlePart, input: &str, expected: &str) -> Option<String> {
+    let label = format!("{} part {} on '{input}'", solution.id(), part.number());
+    match solution.run(part, input) {
+        Ok(answer) if answer.to_string() == expected => None,
+        Ok(answer) => Some(format!("{label}: expected {expected}, got {answer}")),
+        Err(err) => Some(format!("{label}: {err}")),
+    }
+}
+
+#[test]
+fn test_examples() {
+    let mut failures: Vec<String> = Vec::new();
+    for solution in solutions() {
+        for part in PuzzlePart::ALL {
+            let mut checked = 0;
+            for example in solution.examples() {
+                if let Some(expected) = example.expected(part) {
+                    failures.extend(check(solution.as_ref(), part, example.path, expected));
+                    checked += 1;
+                }
+            }
+            if checked == 0 {
+                failures.push(format!(
+                    "{} has no example for part {}.",
+                    solution.id(),
+                    part.number()
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_recorded_answers() {
+    let mut failures: Vec<String> = Vec::new();
+    for solution in solutions() {
+        let id = solution.id();
+        let answers_path = day_dir(id).join("answers.txt");
+        let answers = aoc::answers::read_answers(&answers_path).unwrap();
+
+        for expected in answers {
+            // Real inputs aren't checked in.
+            if !expected.input.exists() {
+                eprintln!(
+                    "Skipping {id} with missing input '{}'.",
+                    expected.input.display()
+                );
+                continue;
+            }
+            failures.extend(check(
+                solution.as_ref(),
+                expected.part,
+                &expected.input.to_string_lossy(),
+                &expected.answer,
+            ));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,37 @@
+//! Runs the `aoc` binary on an input piped to stdin.
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use aoc_cli::day_dir;
+
+fn aoc_with_stdin(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_both_parts_from_stdin() {
+    let input = std::fs::read(day_dir(aoc::DayId::new(2023, 5)).join("example.txt")).unwrap();
+    for args in [
+        &["run", "2023/05", "-"][..],
+        &["bench", "2023/05", "-", "--samples", "1"],
+    ] {
+        let output = aoc_with_stdin(args, &input);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            output.status.success(),
+            "aoc {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(stdout.contains(" 35"), "{stdout}");
+        assert!(stdout.contains(" 46"), "{stdout}");
+    }
+}